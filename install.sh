@@ -74,21 +74,10 @@ echo ""
 echo "Then restart Nushell and run: sdk"
 echo ""
 
-# Create SDKMAN init script for Nushell
-mkdir -p "${HOME}/.sdkman/bin"
-cat > "${HOME}/.sdkman/bin/sdkman-init.nu" << 'EOF'
-# SDKMAN Nushell initialization
-$env.PATH = ($env.PATH | prepend (
-    ls ~/.sdkman/candidates/*/current/bin 
-    | get name
-))
-EOF
-
-echo "✓ Created ~/.sdkman/bin/sdkman-init.nu"
+echo "Then generate the SDKMAN init module:"
+echo "  sdk init --write"
 echo ""
-echo "Add these lines to your ~/.config/nushell/config.nu:"
+echo "And add this line to your ~/.config/nushell/config.nu:"
 echo ""
-echo '# SDKMAN'
-echo '$env.SDKMAN_DIR = ($env.HOME | path join ".sdkman")'
-echo 'source ~/.sdkman/bin/sdkman-init.nu'
+echo 'use ~/.sdkman/bin/sdkman-init.nu *'
 echo ""
@@ -1,7 +1,8 @@
 // Command aliases matching bash SDKMAN
 
-use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, LabeledError, Signature, SyntaxShape};
+use nu_plugin::{DynamicCompletionCall, EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, DynamicSuggestion, LabeledError, Signature, SyntaxShape};
+use nu_protocol::engine::ArgType;
 use crate::SdkmanPlugin;
 use crate::commands::{Install, Uninstall, List, Use, Current, Upgrade, Default, Home, Version};
 
@@ -14,12 +15,21 @@ impl PluginCommand for I {
         Signature::build(self.name())
             .required("candidate", SyntaxShape::String, "Candidate to install")
             .optional("version", SyntaxShape::String, "Version to install")
+            .named("from", SyntaxShape::String, "Install from a URL, directory (linked) or archive and mark the version local", None)
+            .named("local", SyntaxShape::String, "Install from local archive path (same as --from)", Some('l'))
+            .named("platform", SyntaxShape::String, "Platform to install (defaults to the detected platform)", Some('p'))
+            .named("into", SyntaxShape::Filepath, "Install into <dir>/candidates instead of SDKMAN_DIR (e.g. a container image's SDKMAN directory)", None)
+            .switch("download-only", "Only download the archive into the archive cache", None)
             .category(Category::Custom("sdk".into()))
     }
     fn description(&self) -> &str { "Alias for 'sdk install'" }
     fn run(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: &EvaluatedCall, input: nu_protocol::PipelineData) -> Result<nu_protocol::PipelineData, LabeledError> {
         Install.run(plugin, engine, call, input)
     }
+    #[allow(deprecated)]
+    fn get_dynamic_completion(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: DynamicCompletionCall, arg_type: ArgType, experimental: nu_protocol::engine::ExperimentalMarker) -> Option<Vec<DynamicSuggestion>> {
+        Install.get_dynamic_completion(plugin, engine, call, arg_type, experimental)
+    }
 }
 
 // rm -> uninstall
@@ -30,13 +40,20 @@ impl PluginCommand for Rm {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .required("candidate", SyntaxShape::String, "Candidate to uninstall")
-            .required("version", SyntaxShape::String, "Version to uninstall")
+            .rest("versions", SyntaxShape::String, "Versions to uninstall")
+            .switch("all", "Uninstall every installed version of the candidate", Some('a'))
+            .switch("force", "Allow removing the current version (unlinks it)", Some('f'))
+            .named("switch-to", SyntaxShape::String, "Version to make current before removing the current one", Some('s'))
             .category(Category::Custom("sdk".into()))
     }
     fn description(&self) -> &str { "Alias for 'sdk uninstall'" }
     fn run(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: &EvaluatedCall, input: nu_protocol::PipelineData) -> Result<nu_protocol::PipelineData, LabeledError> {
         Uninstall.run(plugin, engine, call, input)
     }
+    #[allow(deprecated)]
+    fn get_dynamic_completion(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: DynamicCompletionCall, arg_type: ArgType, experimental: nu_protocol::engine::ExperimentalMarker) -> Option<Vec<DynamicSuggestion>> {
+        Uninstall.get_dynamic_completion(plugin, engine, call, arg_type, experimental)
+    }
 }
 
 // ls -> list
@@ -47,6 +64,7 @@ impl PluginCommand for Ls {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .optional("candidate", SyntaxShape::String, "Candidate to list versions for")
+            .named("platform", SyntaxShape::String, "Platform to list versions for (defaults to the detected platform)", Some('p'))
             .category(Category::Custom("sdk".into()))
     }
     fn description(&self) -> &str { "Alias for 'sdk list'" }
@@ -70,6 +88,10 @@ impl PluginCommand for U {
     fn run(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: &EvaluatedCall, input: nu_protocol::PipelineData) -> Result<nu_protocol::PipelineData, LabeledError> {
         Use.run(plugin, engine, call, input)
     }
+    #[allow(deprecated)]
+    fn get_dynamic_completion(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: DynamicCompletionCall, arg_type: ArgType, experimental: nu_protocol::engine::ExperimentalMarker) -> Option<Vec<DynamicSuggestion>> {
+        Use.get_dynamic_completion(plugin, engine, call, arg_type, experimental)
+    }
 }
 
 // c -> current
@@ -96,6 +118,9 @@ impl PluginCommand for Ug {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .optional("candidate", SyntaxShape::String, "Candidate to upgrade")
+            .named("within", SyntaxShape::String, "Upgrade scope: major, minor or any (default: major for java, any otherwise)", Some('w'))
+            .switch("any-vendor", "Allow switching to a different vendor", None)
+            .switch("lts", "Only upgrade to LTS releases", None)
             .category(Category::Custom("sdk".into()))
     }
     fn description(&self) -> &str { "Alias for 'sdk upgrade'" }
@@ -119,6 +144,10 @@ impl PluginCommand for D {
     fn run(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: &EvaluatedCall, input: nu_protocol::PipelineData) -> Result<nu_protocol::PipelineData, LabeledError> {
         Default.run(plugin, engine, call, input)
     }
+    #[allow(deprecated)]
+    fn get_dynamic_completion(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: DynamicCompletionCall, arg_type: ArgType, experimental: nu_protocol::engine::ExperimentalMarker) -> Option<Vec<DynamicSuggestion>> {
+        Default.get_dynamic_completion(plugin, engine, call, arg_type, experimental)
+    }
 }
 
 // h -> home
@@ -128,14 +157,19 @@ impl PluginCommand for H {
     fn name(&self) -> &str { "sdk h" }
     fn signature(&self) -> Signature {
         Signature::build(self.name())
-            .required("candidate", SyntaxShape::String, "Candidate name")
-            .required("version", SyntaxShape::String, "Version")
+            .optional("candidate", SyntaxShape::String, "Candidate name")
+            .optional("version", SyntaxShape::String, "Version or version prefix (uses the active version if not specified)")
+            .switch("all", "List the homes of all active candidates", Some('a'))
             .category(Category::Custom("sdk".into()))
     }
     fn description(&self) -> &str { "Alias for 'sdk home'" }
     fn run(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: &EvaluatedCall, input: nu_protocol::PipelineData) -> Result<nu_protocol::PipelineData, LabeledError> {
         Home.run(plugin, engine, call, input)
     }
+    #[allow(deprecated)]
+    fn get_dynamic_completion(&self, plugin: &Self::Plugin, engine: &EngineInterface, call: DynamicCompletionCall, arg_type: ArgType, experimental: nu_protocol::engine::ExperimentalMarker) -> Option<Vec<DynamicSuggestion>> {
+        Home.get_dynamic_completion(plugin, engine, call, arg_type, experimental)
+    }
 }
 
 // v -> version
@@ -0,0 +1,145 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, Span, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::bundle::{self, BundleEntry, Manifest};
+use crate::core::env;
+use std::path::{Path, PathBuf};
+
+pub struct Bundle;
+
+impl PluginCommand for Bundle {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk bundle"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required("subcommand", SyntaxShape::String, "Subcommand: create, install or show")
+            .required("file", SyntaxShape::Filepath, "Bundle file (.tar.gz)")
+            .rest("versions", SyntaxShape::String, "Versions to pack as candidate@version (a bare candidate packs its current version)")
+            .named("rc", SyntaxShape::Filepath, "Pack the versions pinned in this .sdkmanrc", None)
+            .switch("force", "Install a bundle built for another platform", Some('f'))
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Export installed versions to a bundle or install from one"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        let subcommand: String = call.req(0)?;
+        let file = PathBuf::from(call.req::<String>(1)?);
+
+        match subcommand.as_str() {
+            "create" => bundle_create(&file, call),
+            "install" => bundle_install(&file, call),
+            "show" => {
+                let manifest = bundle::read_manifest(&file)
+                    .map_err(|e| LabeledError::new(format!("Failed to read bundle: {}", e)))?;
+                Ok(manifest_record(&manifest, call.head).into_pipeline_data())
+            }
+            cmd => Err(LabeledError::new(format!("Unknown subcommand: {}. Use create, install or show", cmd))),
+        }
+    }
+}
+
+fn bundle_create(file: &Path, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let specs: Vec<String> = call.rest(2)?;
+    let rc: Option<String> = call.get_flag("rc")?;
+
+    let mut entries = specs
+        .iter()
+        .map(|spec| bundle::parse_spec(spec))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| LabeledError::new(e.to_string()))?;
+
+    let rc = rc.map(PathBuf::from).or_else(|| {
+        // Without explicit versions, default to the project's .sdkmanrc
+        entries.is_empty().then(env::working_sdkmanrc).flatten()
+    });
+    if let Some(rc) = rc {
+        entries.extend(bundle::entries_from_sdkmanrc(&rc)
+            .map_err(|e| LabeledError::new(format!("Failed to read {}: {}", rc.display(), e)))?);
+    }
+    if entries.is_empty() {
+        return Err(LabeledError::new("Specify versions to bundle as candidate@version, or use --rc <.sdkmanrc>"));
+    }
+    entries.sort();
+    entries.dedup();
+
+    let platform = env::detect_platform()
+        .map_err(|e| LabeledError::new(e.to_string()))?;
+    let manifest = bundle::create(file, &entries, &platform)
+        .map_err(|e| LabeledError::new(format!("Failed to create bundle: {}", e)))?;
+
+    let size = std::fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+    let mut record = manifest_record(&manifest, call.head);
+    if let Value::Record { val, .. } = &mut record {
+        let val = val.to_mut();
+        val.insert("file", Value::string(file.display().to_string(), call.head));
+        val.insert("size", Value::filesize(size as i64, call.head));
+    }
+    Ok(record.into_pipeline_data())
+}
+
+fn bundle_install(file: &Path, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let force = call.has_flag("force")?;
+    let platform = env::detect_platform()
+        .map_err(|e| LabeledError::new(e.to_string()))?;
+
+    let (_, installed) = bundle::install(file, &platform, force)
+        .map_err(|e| LabeledError::new(format!("Failed to install bundle: {}", e)))?;
+
+    let mut rows = Vec::new();
+    for result in installed {
+        let BundleEntry { candidate, version } = result.entry;
+        if result.installed {
+            env::set_current_version(&candidate, &version)
+                .map_err(|e| LabeledError::new(format!("Failed to set {} {} as current: {}", candidate, version, e)))?;
+        }
+
+        rows.push(Value::record(
+            Record::from_iter(vec![
+                ("candidate".into(), Value::string(candidate, call.head)),
+                ("version".into(), Value::string(version, call.head)),
+                ("status".into(), Value::string(if result.installed { "installed" } else { "already installed" }, call.head)),
+            ]),
+            call.head,
+        ));
+    }
+
+    Ok(Value::list(rows, call.head).into_pipeline_data())
+}
+
+fn manifest_record(manifest: &Manifest, span: Span) -> Value {
+    let entries = manifest.entries
+        .iter()
+        .map(|entry| Value::record(
+            Record::from_iter(vec![
+                ("candidate".into(), Value::string(&entry.candidate, span)),
+                ("version".into(), Value::string(&entry.version, span)),
+            ]),
+            span,
+        ))
+        .collect();
+
+    Value::record(
+        Record::from_iter(vec![
+            ("platform".into(), Value::string(&manifest.platform, span)),
+            ("created".into(), Value::string(&manifest.created, span)),
+            ("entries".into(), Value::list(entries, span)),
+        ]),
+        span,
+    )
+}
@@ -0,0 +1,63 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::archives::ArchiveCache;
+
+pub struct Cache;
+
+impl PluginCommand for Cache {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk cache"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional("candidate", SyntaxShape::String, "Only list archives of this candidate")
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "List downloaded archives kept for reuse"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        let candidate: Option<String> = call.opt(0)?;
+        let cache = ArchiveCache::open()
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+
+        let rows = cache.entries()
+            .into_iter()
+            .filter(|e| candidate.as_ref().is_none_or(|c| &e.candidate == c))
+            .map(|e| {
+                let used_at = e.used_at
+                    .map(|t| chrono::DateTime::<chrono::Utc>::from(t).fixed_offset())
+                    .map_or(Value::nothing(call.head), |t| Value::date(t, call.head));
+                Value::record(
+                    Record::from_iter(vec![
+                        ("candidate".into(), Value::string(e.candidate, call.head)),
+                        ("version".into(), Value::string(e.version, call.head)),
+                        ("platform".into(), Value::string(e.platform, call.head)),
+                        ("size".into(), Value::filesize(e.size as i64, call.head)),
+                        ("sha256".into(), Value::string(e.sha256, call.head)),
+                        ("used_at".into(), used_at),
+                        ("path".into(), Value::string(e.path.display().to_string(), call.head)),
+                    ]),
+                    call.head,
+                )
+            })
+            .collect();
+
+        Ok(Value::list(rows, call.head).into_pipeline_data())
+    }
+}
@@ -0,0 +1,63 @@
+// Dynamic completions shared by the commands taking `<candidate> [version]`
+
+use nu_plugin::{DynamicCompletionCall, EngineInterface};
+use nu_protocol::DynamicSuggestion;
+use nu_protocol::engine::ArgType;
+use std::path::PathBuf;
+use crate::commands::config::load_plugin_config;
+use crate::core::{completions, env};
+
+/// Versions offered for the `version` argument(s).
+pub(crate) enum VersionCompletion {
+    /// Versions installed in `~/.sdkman` (uninstall)
+    Installed,
+    /// Versions usable in the working directory, including the project's
+    /// isolated installations (use, default, home)
+    InProject,
+    /// Versions the API offers for the platform (install)
+    Available,
+}
+
+/// Completes the `candidate` (first) positional and the versions after it;
+/// versions already given (e.g. to uninstall's rest argument) are skipped.
+///
+/// Returns `None` for any other argument so Nushell falls back to its
+/// default completion.
+pub(crate) fn complete_candidate_version(
+    engine: &EngineInterface,
+    call: &DynamicCompletionCall,
+    arg_type: &ArgType,
+    versions: VersionCompletion,
+) -> Option<Vec<DynamicSuggestion>> {
+    // Settings such as the platform come from $env.config.plugins.sdkman
+    load_plugin_config(engine).ok();
+
+    match arg_type {
+        ArgType::Positional(0) => Some(suggestions(completions::candidates())),
+        ArgType::Positional(n) if *n >= 1 => {
+            let candidate = call.call.positional_nth(0)?.as_string()?;
+            let mut versions = match versions {
+                VersionCompletion::Installed => completions::installed_versions(&candidate),
+                VersionCompletion::InProject => {
+                    let cwd = PathBuf::from(engine.get_current_dir().ok()?);
+                    completions::installed_versions_in(&candidate, &env::project_root(&cwd))
+                }
+                VersionCompletion::Available => {
+                    let platform = call.call.get_flag_expr("platform").and_then(|e| e.as_string());
+                    completions::available_versions(&candidate, platform.as_deref())
+                }
+            };
+            let given: Vec<String> = (1..*n).filter_map(|i| call.call.positional_nth(i)?.as_string()).collect();
+            versions.retain(|v| !given.contains(v));
+            Some(suggestions(versions))
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn suggestions(values: impl IntoIterator<Item = impl Into<String>>) -> Vec<DynamicSuggestion> {
+    values
+        .into_iter()
+        .map(|value| DynamicSuggestion { value: value.into(), ..Default::default() })
+        .collect()
+}
@@ -1,8 +1,9 @@
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use crate::constants;
-use nu_protocol::{Category, LabeledError, Signature, Value, IntoPipelineData};
+use nu_protocol::{Category, LabeledError, Record, Signature, Span, SyntaxShape, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
-use crate::core::env;
+use crate::core::config::{self, ConfigFile, PluginSettings, ValueType};
+use crate::core::{archives, env};
+use std::collections::{BTreeSet, HashMap};
 use std::process::Command;
 
 pub struct Config;
@@ -16,34 +17,232 @@ impl PluginCommand for Config {
 
     fn signature(&self) -> Signature {
         Signature::build(self.name())
+            .optional("subcommand", SyntaxShape::String, "Subcommand: get, set, unset, or empty to show the config")
+            .optional("key", SyntaxShape::String, "Config key (e.g. sdkman_auto_answer)")
+            .optional("value", SyntaxShape::String, "Value to set")
+            .switch("edit", "Open the configuration file in $EDITOR", Some('e'))
+            .switch("effective", "Show the settings in effect after merging $env.config.plugins.sdkman", None)
             .category(Category::Custom("sdk".into()))
     }
 
     fn description(&self) -> &str {
-        "Edit SDKMAN configuration file"
+        "Show or change SDKMAN configuration"
     }
 
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
-        let config_file = env::sdkman_dir()
-            .map_err(|e| LabeledError::new(e.to_string()))?
-            .join(constants::ETC_DIR).join(constants::CONFIG_FILE);
+        load_plugin_config(engine)?;
         
-        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+        if call.has_flag("edit")? {
+            return config_edit(call);
+        }
+        if call.has_flag("effective")? {
+            return Ok(effective_record(call.head).into_pipeline_data());
+        }
         
-        Command::new(&editor)
-            .arg(&config_file)
-            .status()
-            .map_err(|e| LabeledError::new(format!("Failed to open editor: {}", e)))?;
+        let subcommand: Option<String> = call.opt(0)?;
+        let key: Option<String> = call.opt(1)?;
+        let value: Option<String> = call.opt(2)?;
         
-        Ok(Value::string(
-            format!("Edited {}", config_file.display()),
-            call.head,
-        ).into_pipeline_data())
+        let mut file = ConfigFile::load()
+            .map_err(|e| LabeledError::new(format!("Failed to read config: {}", e)))?;
+        
+        match (subcommand.as_deref(), key, value) {
+            (None, _, _) => Ok(config_record(&file, call.head).into_pipeline_data()),
+            (Some("get"), Some(key), None) => config_get(&file, &key, call),
+            (Some("set"), Some(key), Some(value)) => {
+                config::validate(&key, &value).map_err(LabeledError::new)?;
+                file.set(&key, &value);
+                save(&file)?;
+                Ok(Value::string(format!("Set {}={}", key, value), call.head).into_pipeline_data())
+            }
+            (Some("unset"), Some(key), None) => {
+                let message = if file.unset(&key) {
+                    save(&file)?;
+                    format!("Removed {}", key)
+                } else {
+                    format!("{} is not set", key)
+                };
+                Ok(Value::string(message, call.head).into_pipeline_data())
+            }
+            (Some("get"), _, _) => Err(LabeledError::new("Usage: sdk config get <key>")),
+            (Some("set"), _, _) => Err(LabeledError::new("Usage: sdk config set <key> <value>")),
+            (Some("unset"), _, _) => Err(LabeledError::new("Usage: sdk config unset <key>")),
+            (Some(cmd), _, _) => Err(LabeledError::new(format!("Unknown subcommand: {}", cmd))),
+        }
+    }
+}
+
+/// Reads `$env.config.plugins.sdkman` and installs it as the plugin settings.
+///
+/// Called at the start of every command so changes to `config.nu` apply
+/// without restarting the plugin. Accepted keys: `api_url`, `offline`,
+/// `platform`, `parallelism`, `auto_env`, `home_vars` (a record mapping
+/// candidates to variable names), `archive_dir`, `archive_cache_size`,
+/// `env_mode` (`shared` or `isolated`) and `relative_links`.
+pub(crate) fn load_plugin_config(engine: &EngineInterface) -> Result<(), LabeledError> {
+    let settings = match engine.get_plugin_config()? {
+        Some(value) => parse_plugin_config(&value)?,
+        None => PluginSettings::default(),
+    };
+    config::set_plugin_settings(settings);
+    Ok(())
+}
+
+/// Parses `$env.config.plugins.sdkman`.
+///
+/// Unknown keys are collected in `ignored_keys` rather than rejected, so a
+/// typo never breaks every command (or prints on every TAB press); they are
+/// listed by `sdk config --effective`. Invalid values of known keys are errors.
+pub fn parse_plugin_config(value: &Value) -> Result<PluginSettings, LabeledError> {
+    let invalid = |key: &str, expected: &str| {
+        LabeledError::new(format!("Invalid plugin config: {} must be {}", key, expected))
+    };
+    let record = value.as_record().map_err(|_| invalid("$env.config.plugins.sdkman", "a record"))?;
+    
+    let mut settings = PluginSettings::default();
+    for (key, value) in record.iter() {
+        match key.as_str() {
+            "api_url" => settings.api_base_url = Some(value.as_str().map_err(|_| invalid(key, "a string"))?.to_string()),
+            "offline" => settings.offline = Some(value.as_bool().map_err(|_| invalid(key, "a bool"))?),
+            "platform" => {
+                let platform = value.as_str().map_err(|_| invalid(key, "a string"))?;
+                settings.platform = Some(env::validate_platform(platform).map_err(|e| LabeledError::new(e.to_string()))?);
+            }
+            "parallelism" => {
+                let n = value.as_int().ok().filter(|n| *n > 0).ok_or_else(|| invalid(key, "a positive int"))?;
+                settings.parallelism = Some(n as usize);
+            }
+            "auto_env" => settings.auto_env = Some(value.as_bool().map_err(|_| invalid(key, "a bool"))?),
+            "home_vars" => {
+                let vars = value.as_record().map_err(|_| invalid(key, "a record"))?;
+                settings.home_vars = vars
+                    .iter()
+                    .map(|(candidate, var)| {
+                        var.as_str()
+                            .map(|v| (candidate.clone(), v.to_string()))
+                            .map_err(|_| invalid(&format!("home_vars.{}", candidate), "a string"))
+                    })
+                    .collect::<Result<HashMap<_, _>, _>>()?;
+            }
+            "archive_dir" => settings.archive_dir = Some(value.as_str().map_err(|_| invalid(key, "a string"))?.into()),
+            "archive_cache_size" => {
+                let size = match value.as_filesize() {
+                    Ok(size) => size.get(),
+                    Err(_) => value.as_int().map_err(|_| invalid(key, "a filesize"))?,
+                };
+                settings.archive_cache_size = Some(u64::try_from(size).map_err(|_| invalid(key, "a non-negative filesize"))?);
+            }
+            "env_mode" => {
+                let mode = value.as_str().map_err(|_| invalid(key, "a string"))?;
+                settings.env_mode = Some(mode.parse().map_err(|e: String| LabeledError::new(e))?);
+            }
+            "relative_links" => settings.relative_links = Some(value.as_bool().map_err(|_| invalid(key, "a bool"))?),
+            other => settings.ignored_keys.push(other.to_string()),
+        }
+    }
+    Ok(settings)
+}
+
+/// Builds a record of every setting in effect, after merging the plugin
+/// settings over `etc/config`.
+fn effective_record(span: Span) -> Value {
+    let effective = config::Config::load();
+    let platform = env::detect_platform().unwrap_or_else(|_| "unknown".to_string());
+    let candidates: BTreeSet<String> = env::get_installed_candidates()
+        .into_iter()
+        .chain(effective.home_vars.keys().cloned())
+        .collect();
+    let home_vars: Record = candidates
+        .into_iter()
+        .map(|c| {
+            let var = effective.home_var(&c);
+            (c, Value::string(var, span))
+        })
+        .collect();
+    
+    let ignored_keys = config::plugin_settings()
+        .ignored_keys
+        .into_iter()
+        .map(|key| Value::string(key, span))
+        .collect();
+    
+    Value::record(
+        Record::from_iter(vec![
+            ("api_url".into(), Value::string(&effective.api_base_url, span)),
+            ("offline".into(), Value::bool(effective.offline, span)),
+            ("platform".into(), Value::string(platform, span)),
+            ("parallelism".into(), Value::int(effective.parallelism as i64, span)),
+            ("auto_env".into(), Value::bool(effective.auto_env, span)),
+            ("insecure_ssl".into(), Value::bool(effective.insecure_ssl, span)),
+            ("curl_connect_timeout".into(), Value::int(effective.curl_connect_timeout as i64, span)),
+            ("curl_max_time".into(), Value::int(effective.curl_max_time as i64, span)),
+            ("rosetta2_compatible".into(), Value::bool(effective.rosetta2_compatible, span)),
+            ("home_vars".into(), Value::record(home_vars, span)),
+            ("archive_dir".into(), archives::archives_dir().map_or(Value::nothing(span), |d| Value::string(d.display().to_string(), span))),
+            ("archive_cache_size".into(), Value::filesize(effective.archive_cache_size as i64, span)),
+            ("env_mode".into(), Value::string(effective.env_mode.to_string(), span)),
+            ("relative_links".into(), Value::bool(effective.relative_links, span)),
+            ("ignored_keys".into(), Value::list(ignored_keys, span)),
+        ]),
+        span,
+    )
+}
+
+fn config_get(file: &ConfigFile, key: &str, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let value = file
+        .get(key)
+        .or_else(|| config::known_key(key).map(|(_, default)| default))
+        .ok_or_else(|| LabeledError::new(format!("{} is not set", key)))?;
+    
+    Ok(typed_value(key, value, call.head).into_pipeline_data())
+}
+
+fn config_edit(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let config_file = config::config_path()
+        .map_err(|e| LabeledError::new(e.to_string()))?;
+    
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    
+    Command::new(&editor)
+        .arg(&config_file)
+        .status()
+        .map_err(|e| LabeledError::new(format!("Failed to open editor: {}", e)))?;
+    
+    Ok(Value::string(
+        format!("Edited {}", config_file.display()),
+        call.head,
+    ).into_pipeline_data())
+}
+
+fn save(file: &ConfigFile) -> Result<(), LabeledError> {
+    file.save()
+        .map_err(|e| LabeledError::new(format!("Failed to write config: {}", e)))
+}
+
+/// Converts the config file entries to a record with typed values.
+fn config_record(file: &ConfigFile, span: Span) -> Value {
+    Value::record(
+        file.entries()
+            .map(|(key, value)| (key.to_string(), typed_value(key, value, span)))
+            .collect::<Record>(),
+        span,
+    )
+}
+
+/// Converts a raw config value to a Nushell value based on the key's known type.
+///
+/// Values that don't match the expected type are kept as strings.
+fn typed_value(key: &str, value: &str, span: Span) -> Value {
+    match config::known_key(key).map(|(ty, _)| ty) {
+        Some(ValueType::Bool) => value.parse().map(|b| Value::bool(b, span)).ok(),
+        Some(ValueType::Int) => value.parse().map(|i| Value::int(i, span)).ok(),
+        None => None,
     }
+    .unwrap_or_else(|| Value::string(value, span))
 }
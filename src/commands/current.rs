@@ -1,7 +1,9 @@
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData};
+use nu_protocol::{Category, LabeledError, Record, Signature, Span, SyntaxShape, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
-use crate::core::env;
+use crate::commands::config::load_plugin_config;
+use crate::core::env::{self, PinStatus};
+use std::path::{Path, PathBuf};
 
 pub struct Current;
 
@@ -22,65 +24,63 @@ impl PluginCommand for Current {
         "Show current version in use"
     }
 
+    fn extra_description(&self) -> &str {
+        "Versions linked in the local .sdkman win over global ones. `pinned` is the version of the \
+        nearest .sdkmanrc; `sdk current | where not matches_pin` lists what is out of sync with it."
+    }
+
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let candidate: Option<String> = call.opt(0)?;
         
+        let project_dir = PathBuf::from(engine.get_current_dir()?);
+
         if let Some(candidate) = candidate {
-            show_current(&candidate, call)
+            show_current(&candidate, &project_dir, call)
         } else {
-            show_all_current(call)
+            show_all_current(&project_dir, call)
         }
     }
 }
 
-fn show_current(candidate: &str, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
-    let current = env::get_current_version(candidate);
-    
-    if let Some(version) = current {
-        Ok(Value::record(
-            Record::from_iter(vec![
-                ("candidate".into(), Value::string(candidate, call.head)),
-                ("version".into(), Value::string(version, call.head)),
-            ]),
-            call.head,
-        ).into_pipeline_data())
-    } else {
-        Err(LabeledError::new(format!("No {} version in use", candidate)))
+fn show_current(candidate: &str, project_dir: &Path, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let status = env::pin_status(candidate, project_dir);
+
+    if status.active.is_none() && status.pinned.is_none() {
+        return Err(LabeledError::new(format!("No {} version in use", candidate)));
     }
+    Ok(current_record(status, call.head).into_pipeline_data())
 }
 
-fn show_all_current(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
-    let candidates_dir = env::candidates_dir()
-        .map_err(|e| LabeledError::new(e.to_string()))?;
-    
-    if !candidates_dir.exists() {
-        return Ok(Value::list(vec![], call.head).into_pipeline_data());
-    }
-    
-    let mut records = Vec::new();
-    
-    if let Ok(entries) = std::fs::read_dir(&candidates_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            if entry.path().is_dir() {
-                let candidate = entry.file_name().to_string_lossy().to_string();
-                if let Some(version) = env::get_current_version(&candidate) {
-                    records.push(Value::record(
-                        Record::from_iter(vec![
-                            ("candidate".into(), Value::string(candidate, call.head)),
-                            ("version".into(), Value::string(version, call.head)),
-                        ]),
-                        call.head,
-                    ));
-                }
-            }
-        }
-    }
-    
-    Ok(Value::list(records, call.head).into_pipeline_data())
+fn show_all_current(project_dir: &Path, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let rows = env::pin_statuses(project_dir)
+        .into_iter()
+        .map(|status| current_record(status, call.head))
+        .collect();
+
+    Ok(Value::list(rows, call.head).into_pipeline_data())
+}
+
+fn current_record(status: PinStatus, span: Span) -> Value {
+    let matches_pin = status.matches_pin();
+    let active = status.active.as_ref();
+    let optional = |s: Option<String>| s.map_or(Value::nothing(span), |s| Value::string(s, span));
+    Value::record(
+        Record::from_iter(vec![
+            ("candidate".into(), Value::string(status.candidate, span)),
+            ("version".into(), optional(active.map(|a| a.version.clone()))),
+            ("source".into(), optional(active.map(|a| a.source.to_string()))),
+            ("home".into(), optional(active.map(|a| a.home.display().to_string()))),
+            ("pinned".into(), optional(status.pinned)),
+            ("matches_pin".into(), Value::bool(matches_pin, span)),
+        ]),
+        span,
+    )
 }
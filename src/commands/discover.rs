@@ -0,0 +1,67 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::discover;
+
+pub struct Discover;
+
+impl PluginCommand for Discover {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk discover"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional("candidate", SyntaxShape::String, "Only look for this candidate")
+            .switch("dry-run", "Report what would be linked without linking anything", Some('n'))
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Find system-installed SDKs and link them as versions"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        let candidate: Option<String> = call.opt(0)?;
+        let dry_run = call.has_flag("dry-run")?;
+
+        let mut rows = Vec::new();
+        for found in discover::discover(candidate.as_deref()) {
+            let (name, status) = match found.linked_as {
+                Some(existing) => (existing, "already linked".to_string()),
+                None if dry_run => (found.name, "would link".to_string()),
+                None => {
+                    let status = match discover::link(&found.candidate, &found.name, &found.path) {
+                        Ok(()) => "linked".to_string(),
+                        Err(e) => format!("failed: {}", e),
+                    };
+                    (found.name, status)
+                }
+            };
+
+            rows.push(Value::record(
+                Record::from_iter(vec![
+                    ("candidate".into(), Value::string(found.candidate, call.head)),
+                    ("version".into(), Value::string(name, call.head)),
+                    ("detected".into(), Value::string(found.version, call.head)),
+                    ("path".into(), Value::string(found.path.display().to_string(), call.head)),
+                    ("status".into(), Value::string(status, call.head)),
+                ]),
+                call.head,
+            ));
+        }
+
+        Ok(Value::list(rows, call.head).into_pipeline_data())
+    }
+}
@@ -0,0 +1,56 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::doctor;
+
+pub struct Doctor;
+
+impl PluginCommand for Doctor {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk doctor"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .switch("fix", "Repair problems that can be fixed safely", None)
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Check the SDKMAN directory for problems"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
+        let fix = call.has_flag("fix")?;
+        
+        let findings = doctor::diagnose(fix)
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+        
+        let rows = findings
+            .into_iter()
+            .map(|f| Value::record(
+                Record::from_iter(vec![
+                    ("severity".into(), Value::string(f.severity.to_string(), call.head)),
+                    ("path".into(), Value::string(f.path.to_string_lossy(), call.head)),
+                    ("issue".into(), Value::string(f.issue, call.head)),
+                    ("fixable".into(), Value::bool(f.fixable, call.head)),
+                    ("fixed".into(), Value::bool(f.fixed, call.head)),
+                ]),
+                call.head,
+            ))
+            .collect();
+        
+        Ok(Value::list(rows, call.head).into_pipeline_data())
+    }
+}
@@ -0,0 +1,142 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, Span, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::constants;
+use crate::core::{archives, env};
+use crate::utils::fs::{self, DirSize};
+use std::path::PathBuf;
+
+pub struct Du;
+
+impl PluginCommand for Du {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk du"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional("candidate", SyntaxShape::String, "Candidate to report (reports all if not specified)")
+            .switch("summary", "Show totals per candidate and for tmp/var/archives instead of per version", Some('s'))
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Show disk usage of installed candidates"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
+        let candidate: Option<String> = call.opt(0)?;
+        let summary = call.has_flag("summary")?;
+        
+        let candidates = match candidate {
+            Some(c) => vec![c],
+            None => env::get_installed_candidates(),
+        };
+        
+        if summary {
+            du_summary(&candidates, call)
+        } else {
+            du_versions(&candidates, call)
+        }
+    }
+}
+
+fn du_versions(candidates: &[String], call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let mut rows = Vec::new();
+    
+    for candidate in candidates {
+        let current = env::get_current_version(candidate);
+        let mut versions = env::get_installed_versions(candidate);
+        versions.sort();
+        
+        for version in versions {
+            let dir = env::candidate_dir(candidate, &version)
+                .map_err(|e| LabeledError::new(e.to_string()))?;
+            let size = fs::dir_size(&dir).unwrap_or_default();
+            let installed_at = env::installed_at(candidate, &version)
+                .map(|t| Value::date(chrono::DateTime::<chrono::Utc>::from(t).fixed_offset(), call.head))
+                .unwrap_or_else(|| Value::nothing(call.head));
+            
+            rows.push(Value::record(
+                Record::from_iter(vec![
+                    ("candidate".into(), Value::string(candidate, call.head)),
+                    ("is_current".into(), Value::bool(current.as_ref() == Some(&version), call.head)),
+                    ("version".into(), Value::string(version, call.head)),
+                    ("size".into(), Value::filesize(size.bytes as i64, call.head)),
+                    ("files".into(), Value::int(size.files as i64, call.head)),
+                    ("installed_at".into(), installed_at),
+                ]),
+                call.head,
+            ));
+        }
+    }
+    
+    Ok(Value::list(rows, call.head).into_pipeline_data())
+}
+
+fn du_summary(candidates: &[String], call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let rows = summary(candidates)
+        .map_err(|e| LabeledError::new(e.to_string()))?
+        .into_iter()
+        .map(|(name, size)| summary_row(&name, size, call.head))
+        .collect();
+    
+    Ok(Value::list(rows, call.head).into_pipeline_data())
+}
+
+/// Computes `sdk du --summary`: the size of each candidate, of `tmp`, `var`
+/// and the archive cache, followed by a `total` entry.
+///
+/// # Errors
+/// Returns error if the SDKMAN directory cannot be determined
+pub fn summary(candidates: &[String]) -> Result<Vec<(String, DirSize)>, Box<dyn std::error::Error>> {
+    let candidates_dir = env::candidates_dir()?;
+    let sdkman_dir = env::sdkman_dir()?;
+    
+    let mut areas: Vec<(&str, PathBuf)> = candidates
+        .iter()
+        .map(|c| (c.as_str(), candidates_dir.join(c)))
+        .chain([
+            (constants::TMP_DIR, sdkman_dir.join(constants::TMP_DIR)),
+            (constants::VAR_DIR, sdkman_dir.join(constants::VAR_DIR)),
+        ])
+        .collect();
+    // The cache may be shared with other SDKMAN directories, but its
+    // downloads take space all the same
+    if let Some(archives) = archives::archives_dir() {
+        areas.push((constants::ARCHIVES_DIR, archives));
+    }
+    
+    let mut sizes = Vec::new();
+    let mut total = DirSize::default();
+    for (name, path) in areas {
+        let size = fs::dir_size(&path).unwrap_or_default();
+        total.bytes += size.bytes;
+        total.files += size.files;
+        sizes.push((name.to_string(), size));
+    }
+    sizes.push(("total".to_string(), total));
+    Ok(sizes)
+}
+
+fn summary_row(name: &str, size: DirSize, span: Span) -> Value {
+    Value::record(
+        Record::from_iter(vec![
+            ("name".into(), Value::string(name, span)),
+            ("size".into(), Value::filesize(size.bytes as i64, span)),
+            ("files".into(), Value::int(size.files as i64, span)),
+        ]),
+        span,
+    )
+}
@@ -1,8 +1,12 @@
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
 use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
 use crate::constants;
-use crate::core::{env, install};
+use crate::core::{activation, env, install};
+use crate::core::activation::Shell;
+use crate::core::config::{Config, EnvMode};
+use crate::core::hooks::HookReport;
 use std::fs;
 use std::collections::HashMap;
 
@@ -18,6 +22,7 @@ impl PluginCommand for Env {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .optional("subcommand", SyntaxShape::String, "Subcommand: init, install, clear, or empty to load")
+            .switch("isolated", "With install: install into the project's .sdkman instead of ~/.sdkman", None)
             .category(Category::Custom("sdk".into()))
     }
 
@@ -28,10 +33,12 @@ impl PluginCommand for Env {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let subcommand: Option<String> = call.opt(0)?;
         
         match subcommand.as_deref() {
@@ -89,69 +96,27 @@ fn env_init(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledEr
     fs::create_dir_all(local_sdkman.join(constants::CANDIDATES_DIR))
         .map_err(|e| LabeledError::new(format!("Failed to create .sdkman directory: {}", e)))?;
     
-    // Create Nushell activation script
-    let env_nu = r#"# SDKMAN Local Environment
-# Source this file to activate local SDK versions
-
-export-env {
-    let local_bins = (
-        try {
-            ls .sdkman/candidates/*/current/bin 
-            | get name 
-            | path expand
-        } catch {
-            []
-        }
-    )
-    
-    if ($local_bins | length) > 0 {
-        $env.PATH = ($env.PATH | prepend $local_bins)
-    }
-}
-"#;
-    
-    fs::write(local_sdkman.join("env.nu"), env_nu)
-        .map_err(|e| LabeledError::new(format!("Failed to create env.nu: {}", e)))?;
-    
-    // Create bash/zsh activation script
-    let env_sh = r#"# SDKMAN Local Environment
-# Source this file: source .sdkman/env.sh
-
-# Add local SDK bins to PATH
-for candidate_bin in .sdkman/candidates/*/current/bin; do
-    if [ -d "$candidate_bin" ]; then
-        export PATH="$candidate_bin:$PATH"
-    fi
-done
-"#;
-    
-    fs::write(local_sdkman.join("env.sh"), env_sh)
-        .map_err(|e| LabeledError::new(format!("Failed to create env.sh: {}", e)))?;
-    
-    // Create fish shell activation script
-    let env_fish = r#"# SDKMAN Local Environment
-# Source this file: source .sdkman/env.fish
-
-# Add local SDK bins to PATH
-for candidate_bin in .sdkman/candidates/*/current/bin
-    if test -d $candidate_bin
-        set -gx PATH $candidate_bin $PATH
-    end
-end
-"#;
-    
-    fs::write(local_sdkman.join("env.fish"), env_fish)
-        .map_err(|e| LabeledError::new(format!("Failed to create env.fish: {}", e)))?;
-    
+    // Generate activation scripts for every supported shell
+    activation::write_local_scripts(&current_dir)
+        .map_err(|e| LabeledError::new(format!("Failed to create activation scripts: {}", e)))?;
+    
+    let activate: Vec<String> = Shell::ALL
+        .iter()
+        .map(|shell| {
+            let script = format!("{}/{}", constants::SDKMAN_DIR_NAME, shell.script_name());
+            format!("  {}: {}", shell.name(), shell.activate_command(&script))
+        })
+        .collect();
     let message = format!(
         "Created local SDKMAN environment in {}\n\n\
-        To activate:\n  \
-        Nushell: source .sdkman/env.nu\n  \
-        Bash/Zsh: source .sdkman/env.sh\n  \
-        Fish: source .sdkman/env.fish\n\n\
-        To install SDKs locally:\n  source .sdkman/env.nu\n  sdk env install\n\n\
-        Note: SDKs are installed globally but symlinked locally for isolation.",
-        current_dir.display()
+        To activate:\n{}\n\
+        To deactivate: {}\n\n\
+        To install SDKs locally:\n  sdk env install\n\n\
+        Note: SDKs are installed globally but symlinked locally for isolation;\n\
+        use 'sdk env install --isolated' to install them into .sdkman instead.",
+        current_dir.display(),
+        activate.join("\n"),
+        activation::DEACTIVATE_FN
     );
     
     Ok(Value::string(message, call.head).into_pipeline_data())
@@ -176,19 +141,32 @@ fn env_install(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, Labele
     let platform = env::detect_platform()
         .map_err(|e| LabeledError::new(e.to_string()))?;
     
-    let is_local = env::is_local_env();
+    let config = Config::load();
+    let isolated_dir = if call.has_flag("isolated")? || config.env_mode == EnvMode::Isolated {
+        let local_dir = current_dir.join(constants::SDKMAN_DIR_NAME);
+        fs::create_dir_all(local_dir.join(constants::CANDIDATES_DIR))
+            .map_err(|e| LabeledError::new(format!("Failed to create .sdkman directory: {}", e)))?;
+        Some(local_dir)
+    } else {
+        None
+    };
     
-    for (candidate, version) in versions {
-        // Always install to global location
-        if env::is_installed(&candidate, &version) {
-            results.push(format!("{} {} already installed", candidate, version));
-        } else {
-            match install::install_candidate(&candidate, &version, &platform) {
-                Ok(_) => results.push(format!("Installed {} {}", candidate, version)),
-                Err(e) => {
-                    errors.push(format!("Failed to install {} {}: {}", candidate, version, e));
-                    continue; // Skip setting current version if install failed
-                }
+    let is_local = env::is_local_env();
+    let mut versions: Vec<(String, String)> = versions.into_iter().collect();
+    versions.sort();
+    let installs = install_all(&versions, &platform, config.parallelism, isolated_dir.as_deref());
+    
+    for ((candidate, version), installed) in versions.into_iter().zip(installs) {
+        // Install to the global location unless isolated
+        match installed {
+            Ok(Some(hooks)) => {
+                results.push(format!("Installed {} {}", candidate, version));
+                results.extend(hooks.iter().map(|hook| hook.to_string()));
+            }
+            Ok(None) => results.push(format!("{} {} already installed", candidate, version)),
+            Err(e) => {
+                errors.push(format!("Failed to install {} {}: {}", candidate, version, e));
+                continue; // Skip setting current version if install failed
             }
         }
         
@@ -216,6 +194,48 @@ fn env_install(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, Labele
     Ok(Value::string(output, call.head).into_pipeline_data())
 }
 
+/// Installs the missing versions, `parallelism` at a time, globally or into
+/// the `isolated_dir` local environment, then refreshes the init script and
+/// shims once.
+///
+/// Returns, in input order, the hook reports of each new install (`None` if
+/// the version was already installed) or an install error message.
+fn install_all(
+    versions: &[(String, String)],
+    platform: &str,
+    parallelism: usize,
+    isolated_dir: Option<&std::path::Path>,
+) -> Vec<Result<Option<Vec<HookReport>>, String>> {
+    let mut outcomes = Vec::with_capacity(versions.len());
+    for chunk in versions.chunks(parallelism.max(1)) {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|(candidate, version)| scope.spawn(move || {
+                    let install_dir = match isolated_dir {
+                        Some(local_dir) => env::local_candidate_dir(local_dir, candidate, version),
+                        None => env::candidate_dir(candidate, version).map_err(|e| e.to_string())?,
+                    };
+                    if install_dir.exists() {
+                        return Ok(None);
+                    }
+                    install::install_candidate_deferred(candidate, version, platform, &install_dir)
+                        .map(Some)
+                        .map_err(|e| e.to_string())
+                }))
+                .collect();
+            outcomes.extend(handles.into_iter().map(|h| {
+                h.join().unwrap_or_else(|_| Err("install thread panicked".to_string()))
+            }));
+        });
+    }
+    // Shared files are regenerated once, after every install thread is done
+    if outcomes.iter().any(|o| matches!(o, Ok(Some(_)))) {
+        install::refresh_after_install();
+    }
+    outcomes
+}
+
 fn env_load(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
     let current_dir = std::env::var(constants::ENV_PWD)
         .ok()
@@ -234,7 +254,9 @@ fn env_load(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledEr
     let is_local = env::is_local_env();
     
     for (candidate, version) in versions {
-        if !env::is_installed(&candidate, &version) {
+        let installed = env::is_installed(&candidate, &version)
+            || (is_local && env::is_installed_locally(&candidate, &version));
+        if !installed {
             results.push(format!("{} {} not installed", candidate, version));
         } else {
             if is_local {
@@ -307,18 +329,5 @@ fn parse_sdkmanrc(path: &std::path::Path) -> Result<HashMap<String, String>, Lab
 
 /// Parse .sdkmanrc content (extracted for testing)
 pub fn parse_sdkmanrc_content(content: &str) -> Result<HashMap<String, String>, LabeledError> {
-    let mut versions = HashMap::new();
-    
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-        
-        if let Some((key, value)) = line.split_once('=') {
-            versions.insert(key.trim().to_string(), value.trim().to_string());
-        }
-    }
-    
-    Ok(versions)
+    Ok(env::parse_sdkmanrc(content))
 }
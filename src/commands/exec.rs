@@ -0,0 +1,104 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{ByteStream, ByteStreamType, Category, LabeledError, PipelineData, Signature, SyntaxShape, Value};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::exec;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+
+pub struct Exec;
+
+impl PluginCommand for Exec {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk exec"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .rest("args", SyntaxShape::Any, "candidate@version specs, then -- and the command to run")
+            .allows_unknown_args()
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Run a command with specific SDK versions without changing current versions"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Versions pinned in .sdkmanrc are used by default; specs before -- add to or override them. \
+        A bare candidate selects its current version. Without --, all arguments form the command. \
+        A non-zero exit status ends the output stream with an error whose message names the exit code; \
+        the code itself is not available as $env.LAST_EXIT_CODE."
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        let args: Vec<String> = call
+            .rest::<Value>(0)?
+            .into_iter()
+            .map(|v| v.coerce_into_string().map_err(|e| LabeledError::new(e.to_string())))
+            .collect::<Result<_, _>>()?;
+        let (specs, command) = match args.iter().position(|a| a == "--") {
+            Some(i) => (&args[..i], &args[i + 1..]),
+            None => (&args[..0], &args[..]),
+        };
+        let (program, program_args) = command
+            .split_first()
+            .ok_or_else(|| LabeledError::new("Usage: sdk exec [candidate@version...] -- <command> [args...]"))?;
+
+        let cwd = PathBuf::from(engine.get_current_dir()?);
+        let toolchain = exec::resolve_toolchain(specs, &cwd)
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+        let vars = exec::command_env(&toolchain, &string_env(engine.get_env_vars()?))
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+
+        let output = exec::spawn(program, program_args, &cwd, &vars, stdin(input)?)
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+
+        Ok(PipelineData::byte_stream(
+            ByteStream::read(output, call.head, engine.signals().clone(), ByteStreamType::Unknown),
+            None,
+        ))
+    }
+}
+
+/// Converts Nushell's environment into strings for a child process; lists
+/// (e.g. PATH) are joined with the platform's path separator.
+pub(crate) fn string_env(vars: HashMap<String, Value>) -> HashMap<String, String> {
+    vars.into_iter()
+        .filter_map(|(name, value)| {
+            let value = match value {
+                Value::List { vals, .. } => {
+                    let paths: Vec<String> = vals.into_iter().filter_map(|v| v.coerce_into_string().ok()).collect();
+                    std::env::join_paths(paths).ok()?.to_string_lossy().into_owned()
+                }
+                other => other.coerce_into_string().ok()?,
+            };
+            Some((name, value))
+        })
+        .collect()
+}
+
+/// Turns the pipeline input into the command's stdin.
+fn stdin(input: PipelineData) -> Result<Option<Box<dyn Read + Send>>, LabeledError> {
+    Ok(match input {
+        PipelineData::Empty => None,
+        PipelineData::ByteStream(stream, _) => stream.reader().map(|r| Box::new(r) as Box<dyn Read + Send>),
+        PipelineData::Value(Value::Nothing { .. }, _) => None,
+        PipelineData::Value(Value::Binary { val, .. }, _) => Some(Box::new(std::io::Cursor::new(val))),
+        other => {
+            let text = other.collect_string("\n", &nu_protocol::Config::default())?;
+            Some(Box::new(std::io::Cursor::new(text.into_bytes())))
+        }
+    })
+}
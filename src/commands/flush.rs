@@ -2,7 +2,9 @@ use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
 use crate::constants;
 use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
 use crate::core::env;
+use crate::core::archives::ArchiveCache;
 use std::fs;
 
 pub struct Flush;
@@ -16,21 +18,23 @@ impl PluginCommand for Flush {
 
     fn signature(&self) -> Signature {
         Signature::build(self.name())
-            .optional("target", SyntaxShape::String, "What to flush: tmp, metadata, version, or all")
+            .optional("target", SyntaxShape::String, "What to flush: tmp, metadata, version, archives, or all")
             .category(Category::Custom("sdk".into()))
     }
 
     fn description(&self) -> &str {
-        "Clear caches (tmp/metadata/version)"
+        "Clear caches (tmp/metadata/version/archives)"
     }
 
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let target: Option<String> = call.opt(0)?;
         
         let sdkman_dir = env::sdkman_dir()
@@ -52,8 +56,17 @@ impl PluginCommand for Flush {
                     flushed.push("version file".to_string());
                 }
             }
+            Some(constants::ARCHIVES_DIR) => {
+                let cache = ArchiveCache::open()
+                    .map_err(|e| LabeledError::new(e.to_string()))?;
+                let count = cache.clear()
+                    .map_err(|e| LabeledError::new(format!("Failed to clear archive cache: {}", e)))?;
+                if count > 0 {
+                    flushed.push(format!("{} ({} items)", constants::ARCHIVES_DIR, count));
+                }
+            }
             _ => {
-                // Flush all
+                // Flush all (downloaded archives are kept; flush them explicitly)
                 flush_dir(&sdkman_dir.join(constants::TMP_DIR), &mut flushed)?;
                 flush_dir(&sdkman_dir.join(constants::VAR_DIR).join(constants::METADATA_DIR), &mut flushed)?;
             }
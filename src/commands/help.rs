@@ -32,20 +32,33 @@ impl PluginCommand for Sdk {
 Usage: sdk <command> [args]
 
 Commands:
-  list (ls) [candidate]              List available candidates or versions
-  install (i) <candidate> [version]  Install a candidate version
-  uninstall (rm) <candidate> <version> Uninstall a candidate version
+  list (ls) [candidate] [--platform] List available candidates or versions
+  install (i) <candidate> [version] [--platform] Install a candidate version
+  uninstall (rm) <candidate> <version>... Uninstall candidate versions
   use (u) <candidate> <version>      Set a candidate version as current
   default (d) <candidate> [version]  Set default version for a candidate
   current (c) [candidate]            Show current version in use
   upgrade (ug) [candidate]           Upgrade candidate to latest version
-  home (h) <candidate> <version>     Print home directory path
+  home (h) <candidate> [version]     Print home directory path (--all for every candidate)
   env [init|install|clear]           Manage .sdkmanrc files
   version (v)                        Show SDKMAN plugin version
   update                             Update local candidate cache
-  flush [tmp|metadata|version]       Clear caches
-  config                             Edit configuration file
+  flush [tmp|metadata|version|archives] Clear caches
+  cache [candidate]                  List downloaded archives kept for reuse
+  link <candidate> <name> <path>     Register an existing installation as a version
+  discover [candidate] [--dry-run]   Find and link system-installed SDKs
+  init [--write]                     Generate the Nushell activation module
+  exec [c@v...] -- <cmd> [args]      Run a command with specific versions
+  which <binary> [--all]             Show which candidate and version a binary belongs to
+  shims [rehash]                     List or regenerate version-resolving shims
+  du [candidate] [--summary]         Show disk usage of installed candidates
+  doctor [--fix]                     Check the SDKMAN directory for problems
+  config [get|set|unset] [key] [value] Show or change configuration
+  config --effective                 Show settings merged with $env.config.plugins.sdkman
   offline [enable|disable]           Enable/disable offline mode
+  outdated [candidate]               Compare installed versions with available ones
+  prune [candidate] --keep <n>       Remove unused installed versions
+  bundle [create|install|show] <file> Export or install an offline bundle
 
 Examples:
   sdk list                           List all candidates
@@ -53,12 +66,23 @@ Examples:
   sdk install java                   Install latest Java
   sdk install java 17                Install Java 17
   sdk install java 17 --local /path  Install from local archive
+  sdk install java sys --from <dir>  Link an existing installation as a local version
+  sdk i java <v> -p linuxmusl --into <dir> Install an Alpine build into another SDKMAN dir
   sdk use java 17                    Switch to Java 17
   sdk default java 17                Set Java 17 as default
   sdk current                        Show all current versions
   sdk home java 17                   Show Java 17 home directory
+  $env.JAVA_HOME = (sdk home java)   Use the active Java's home directory
   sdk env init                       Create .sdkmanrc in current directory
-  sdk upgrade java                   Upgrade Java to latest
+  sdk env install --isolated         Install .sdkmanrc versions into the project's .sdkman
+  sdk exec java@21.0.1 -- mvn -v     Run Maven with Java 21 without switching versions
+  sdk shims rehash                   Create shims in ~/.sdkman/shims for installed binaries
+  sdk outdated                       Show which installed candidates have newer versions
+  sdk prune --keep 2 --dry-run       Preview removing all but the 2 newest versions
+  sdk bundle create sdks.tar.gz      Bundle the versions pinned in .sdkmanrc
+  sdk upgrade java                   Upgrade Java within its major version and vendor
+  sdk upgrade java --within any      Upgrade Java to the newest release of its vendor
+  sdk upgrade gradle                 Upgrade Gradle to its newest release (any major)
 
 Aliases: i, rm, ls, u, c, ug, d, h, v
 
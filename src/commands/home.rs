@@ -1,7 +1,11 @@
-use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
+use nu_plugin::{DynamicCompletionCall, EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData, DynamicSuggestion};
+use nu_protocol::engine::ArgType;
 use crate::SdkmanPlugin;
-use crate::core::env;
+use crate::commands::completions::{self, VersionCompletion};
+use crate::commands::config::load_plugin_config;
+use crate::core::{env, version};
+use std::path::PathBuf;
 
 pub struct Home;
 
@@ -14,8 +18,9 @@ impl PluginCommand for Home {
 
     fn signature(&self) -> Signature {
         Signature::build(self.name())
-            .required("candidate", SyntaxShape::String, "Candidate name")
-            .required("version", SyntaxShape::String, "Version")
+            .optional("candidate", SyntaxShape::String, "Candidate name")
+            .optional("version", SyntaxShape::String, "Version or version prefix (uses the active version if not specified)")
+            .switch("all", "List the homes of all active candidates", Some('a'))
             .category(Category::Custom("sdk".into()))
     }
 
@@ -23,29 +28,81 @@ impl PluginCommand for Home {
         "Print home directory path of installed candidate version"
     }
 
+    fn extra_description(&self) -> &str {
+        "Without a version, the active version is used: the local .sdkman first, then the global \
+        current version. Versions may be given as a prefix, e.g. 17 or 17-tem for the newest \
+        installed match."
+    }
+
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
-        let candidate: String = call.req(0)?;
-        let version: String = call.req(1)?;
-        
-        if !env::is_installed(&candidate, &version) {
-            return Err(LabeledError::new(format!(
-                "{} {} is not installed. Run 'sdk install {} {}' first",
-                candidate, version, candidate, version
-            )));
+        load_plugin_config(engine)?;
+
+        // The project's .sdkman sits next to its .sdkmanrc, also from subdirectories
+        let project_dir = env::project_root(&PathBuf::from(engine.get_current_dir()?));
+
+        if call.has_flag("all")? {
+            let rows = env::active_versions(&project_dir)
+                .into_iter()
+                .map(|active| {
+                    Value::record(
+                        Record::from_iter(vec![
+                            ("candidate".into(), Value::string(active.candidate, call.head)),
+                            ("version".into(), Value::string(active.version, call.head)),
+                            ("source".into(), Value::string(active.source.to_string(), call.head)),
+                            ("home".into(), Value::string(active.home.display().to_string(), call.head)),
+                        ]),
+                        call.head,
+                    )
+                })
+                .collect();
+            return Ok(Value::list(rows, call.head).into_pipeline_data());
         }
-        
-        let home_dir = env::candidate_dir(&candidate, &version)
-            .map_err(|e| LabeledError::new(e.to_string()))?;
-        
+
+        let candidate: String = call
+            .opt(0)?
+            .ok_or_else(|| LabeledError::new("Usage: sdk home <candidate> [version], or sdk home --all"))?;
+        let spec: Option<String> = call.opt(1)?;
+
+        let home_dir = match spec {
+            Some(spec) => {
+                let installed = env::installed_versions_in(&candidate, &project_dir);
+                version::match_version(&spec, &installed)
+                    .and_then(|version| env::version_home(&candidate, &version, &project_dir))
+                    .ok_or_else(|| LabeledError::new(format!(
+                        "{} {} is not installed. Run 'sdk install {} {}' first",
+                        candidate, spec, candidate, spec
+                    )))?
+            }
+            None => env::active_version(&candidate, &project_dir)
+                .map(|active| active.home)
+                .ok_or_else(|| LabeledError::new(format!(
+                    "No {} version in use. Run 'sdk use {} <version>' or pass a version",
+                    candidate, candidate
+                )))?,
+        };
+
         Ok(Value::string(
             home_dir.to_string_lossy().to_string(),
             call.head,
         ).into_pipeline_data())
     }
+
+    #[allow(deprecated)]
+    fn get_dynamic_completion(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: DynamicCompletionCall,
+        arg_type: ArgType,
+        _experimental: nu_protocol::engine::ExperimentalMarker,
+    ) -> Option<Vec<DynamicSuggestion>> {
+        completions::complete_candidate_version(engine, &call, &arg_type, VersionCompletion::InProject)
+    }
 }
+
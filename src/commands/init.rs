@@ -0,0 +1,51 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Signature, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::init::{self, InitModule};
+
+pub struct Init;
+
+impl PluginCommand for Init {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk init"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .switch("write", "Write the module to ~/.sdkman/bin/sdkman-init.nu instead of printing it", Some('w'))
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Generate the Nushell activation module (PATH, HOME variables, auto-env hook)"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        if !call.has_flag("write")? {
+            let module = InitModule::load()
+                .map_err(|e| LabeledError::new(e.to_string()))?;
+            return Ok(Value::string(module.render(), call.head).into_pipeline_data());
+        }
+
+        let path = init::init_script_path()
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+        init::write_init_script(&path)
+            .map_err(|e| LabeledError::new(format!("Failed to write {}: {}", path.display(), e)))?;
+
+        Ok(Value::string(
+            format!("Wrote {}\nAdd to config.nu: use {} *", path.display(), path.display()),
+            call.head,
+        ).into_pipeline_data())
+    }
+}
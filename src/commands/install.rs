@@ -1,7 +1,12 @@
-use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
+use nu_plugin::{DynamicCompletionCall, EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData, DynamicSuggestion};
+use nu_protocol::engine::ArgType;
 use crate::SdkmanPlugin;
+use crate::commands::completions::{self, VersionCompletion};
+use crate::commands::config::load_plugin_config;
+use crate::constants;
 use crate::core::{api, env, install};
+use std::path::{Path, PathBuf};
 
 pub struct Install;
 
@@ -16,7 +21,11 @@ impl PluginCommand for Install {
         Signature::build(self.name())
             .required("candidate", SyntaxShape::String, "Candidate to install")
             .optional("version", SyntaxShape::String, "Version to install (defaults to latest)")
-            .named("local", SyntaxShape::String, "Install from local archive path", Some('l'))
+            .named("from", SyntaxShape::String, "Install from a URL, directory (linked) or archive and mark the version local", None)
+            .named("local", SyntaxShape::String, "Install from local archive path (same as --from)", Some('l'))
+            .named("platform", SyntaxShape::String, "Platform to install (defaults to the detected platform)", Some('p'))
+            .named("into", SyntaxShape::Filepath, "Install into <dir>/candidates instead of SDKMAN_DIR (e.g. a container image's SDKMAN directory)", None)
+            .switch("download-only", "Only download the archive into the archive cache", None)
             .category(Category::Custom("sdk".into()))
     }
 
@@ -27,27 +36,65 @@ impl PluginCommand for Install {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let candidate: String = call.req(0)?;
         let version: Option<String> = call.opt(1)?;
-        let local_path: Option<String> = call.get_flag("local")?;
+        let from: Option<String> = match call.get_flag("from")? {
+            Some(from) => Some(from),
+            None => call.get_flag("local")?,
+        };
+        let requested_platform: Option<String> = call.get_flag("platform")?;
+        let into: Option<String> = call.get_flag("into")?;
+        let download_only = call.has_flag("download-only")?;
+        
+        if from.is_some() && (requested_platform.is_some() || into.is_some() || download_only) {
+            return Err(LabeledError::new("--from installs a local source as is; it cannot be combined with --platform, --into or --download-only"));
+        }
         
         api::validate_candidate(&candidate)
             .map_err(|e| LabeledError::new(e.to_string()))?;
         
-        let platform = env::detect_platform()
+        let platform = env::resolve_platform(requested_platform.as_deref())
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+        let native_platform = env::resolve_platform(None)
             .map_err(|e| LabeledError::new(e.to_string()))?;
         
         let install_version = if let Some(v) = version {
             v
+        } else if from.is_some() {
+            return Err(LabeledError::new("A version name is required when installing with --from"));
         } else {
             api::get_default_version(&candidate, &platform)
                 .map_err(|e| LabeledError::new(format!("Failed to get default version: {}", e)))?
         };
         
+        if download_only {
+            let archive = install::download_to_cache(&candidate, &install_version, &platform)
+                .map_err(|e| LabeledError::new(format!("Download failed: {}", e)))?;
+            return Ok(Value::string(
+                format!("{} {} ({}) cached at {}", candidate, install_version, platform, archive.display()),
+                call.head,
+            ).into_pipeline_data());
+        }
+        
+        if let Some(into) = into {
+            let dir = PathBuf::from(engine.get_current_dir()?).join(into);
+            return install_into(&candidate, &install_version, &platform, &dir, call);
+        }
+        
+        // A foreign build in SDKMAN_DIR would replace the host's toolchain
+        if platform != native_platform {
+            return Err(LabeledError::new(format!(
+                "{} is not this machine's platform ({}). Use --into <dir> to install it into another SDKMAN directory, or --download-only to fill the archive cache",
+                platform, native_platform
+            )));
+        }
+        
         if env::is_installed(&candidate, &install_version) {
             return Err(LabeledError::new(format!(
                 "{} {} is already installed",
@@ -55,20 +102,57 @@ impl PluginCommand for Install {
             )));
         }
         
-        if let Some(local) = local_path {
-            install::install_local(&candidate, &install_version, std::path::Path::new(&local))
-                .map_err(|e| LabeledError::new(format!("Local install failed: {}", e)))?;
+        let hooks = if let Some(from) = from {
+            let source = install::InstallSource::parse(&from, &PathBuf::from(engine.get_current_dir()?))
+                .map_err(|e| LabeledError::new(e.to_string()))?;
+            install::install_from(&candidate, &install_version, &source)
+                .map_err(|e| LabeledError::new(format!("Local install failed: {}", e)))?
         } else {
             install::install_candidate(&candidate, &install_version, &platform)
-                .map_err(|e| LabeledError::new(format!("Install failed: {}", e)))?;
-        }
+                .map_err(|e| LabeledError::new(format!("Install failed: {}", e)))?
+        };
         
         env::set_current_version(&candidate, &install_version)
             .map_err(|e| LabeledError::new(format!("Failed to set current version: {}", e)))?;
         
-        Ok(Value::string(
-            format!("{} {} installed successfully", candidate, install_version),
-            call.head,
-        ).into_pipeline_data())
+        let mut message = format!("{} {} installed successfully", candidate, install_version);
+        for hook in hooks {
+            message.push_str(&format!("\n{}", hook));
+        }
+        
+        Ok(Value::string(message, call.head).into_pipeline_data())
+    }
+
+    #[allow(deprecated)]
+    fn get_dynamic_completion(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: DynamicCompletionCall,
+        arg_type: ArgType,
+        _experimental: nu_protocol::engine::ExperimentalMarker,
+    ) -> Option<Vec<DynamicSuggestion>> {
+        if matches!(&arg_type, ArgType::Flag(flag) if flag == "platform") {
+            return Some(completions::suggestions(env::PLATFORMS.iter().copied()));
+        }
+        completions::complete_candidate_version(engine, &call, &arg_type, VersionCompletion::Available)
+    }
+}
+
+/// Installs a version into `<dir>/candidates/<candidate>/<version>`, leaving
+/// the host's versions and `current` links untouched.
+fn install_into(candidate: &str, version: &str, platform: &str, dir: &Path, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let install_dir = dir.join(constants::CANDIDATES_DIR).join(candidate).join(version);
+    if install_dir.exists() {
+        return Err(LabeledError::new(format!("{} {} is already installed in {}", candidate, version, dir.display())));
+    }
+    
+    let hooks = install::install_candidate_into(candidate, version, platform, &install_dir)
+        .map_err(|e| LabeledError::new(format!("Install failed: {}", e)))?;
+    
+    let mut message = format!("{} {} ({}) installed into {}", candidate, version, platform, install_dir.display());
+    for hook in hooks {
+        message.push_str(&format!("\n{}", hook));
     }
+    Ok(Value::string(message, call.head).into_pipeline_data())
 }
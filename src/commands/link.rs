@@ -0,0 +1,50 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::discover;
+use std::path::PathBuf;
+
+pub struct Link;
+
+impl PluginCommand for Link {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk link"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required("candidate", SyntaxShape::String, "Candidate the installation belongs to")
+            .required("name", SyntaxShape::String, "Version name to register it under (e.g. system-17)")
+            .required("path", SyntaxShape::Filepath, "Existing installation directory")
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Register an existing installation as a linked version"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        let candidate: String = call.req(0)?;
+        let name: String = call.req(1)?;
+        let path = PathBuf::from(engine.get_current_dir()?).join(call.req::<String>(2)?);
+
+        discover::link(&candidate, &name, &path)
+            .map_err(|e| LabeledError::new(format!("Failed to link {} {}: {}", candidate, name, e)))?;
+
+        Ok(Value::string(
+            format!("Linked {} {} to {}", candidate, name, path.display()),
+            call.head,
+        ).into_pipeline_data())
+    }
+}
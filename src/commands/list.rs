@@ -1,7 +1,9 @@
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
 use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
 use crate::core::{api, env};
+use std::collections::HashSet;
 
 pub struct List;
 
@@ -15,6 +17,7 @@ impl PluginCommand for List {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .optional("candidate", SyntaxShape::String, "Candidate to list versions for")
+            .named("platform", SyntaxShape::String, "Platform to list versions for (defaults to the detected platform)", Some('p'))
             .category(Category::Custom("sdk".into()))
     }
 
@@ -25,14 +28,17 @@ impl PluginCommand for List {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let candidate: Option<String> = call.opt(0)?;
+        let platform: Option<String> = call.get_flag("platform")?;
         
         if let Some(candidate) = candidate {
-            list_versions(&candidate, call)
+            list_versions(&candidate, platform.as_deref(), call)
         } else {
             list_candidates(call)
         }
@@ -46,11 +52,11 @@ fn list_candidates(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, La
     Ok(Value::string(text, call.head).into_pipeline_data())
 }
 
-fn list_versions(candidate: &str, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+fn list_versions(candidate: &str, platform: Option<&str>, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
     api::validate_candidate(candidate)
         .map_err(|e| LabeledError::new(e.to_string()))?;
     
-    let platform = env::detect_platform()
+    let platform = env::resolve_platform(platform)
         .map_err(|e| LabeledError::new(e.to_string()))?;
     let current = env::get_current_version(candidate).unwrap_or_default();
     let installed = env::get_installed_versions(candidate).join(",");
@@ -58,7 +64,14 @@ fn list_versions(candidate: &str, call: &EvaluatedCall) -> Result<nu_protocol::P
     let text = api::get_versions_list(candidate, &platform, &current, &installed)
         .map_err(|e| LabeledError::new(format!("Failed to fetch versions: {}", e)))?;
     
+    let mut local_versions: Vec<String> = env::get_installed_versions(candidate)
+        .into_iter()
+        .filter(|v| env::is_local_version(candidate, v))
+        .collect();
+    local_versions.sort();
+    
     let mut rows = Vec::new();
+    let mut listed = HashSet::new();
     let mut current_vendor = String::new();
     
     for line in text.lines() {
@@ -86,6 +99,8 @@ fn list_versions(candidate: &str, call: &EvaluatedCall) -> Result<nu_protocol::P
         if identifier.is_empty() {
             continue;
         }
+        let status = if local_versions.iter().any(|v| v == identifier) { "local" } else { status };
+        listed.insert(identifier.to_string());
         
         rows.push(Value::record(
             Record::from_iter(vec![
@@ -100,6 +115,22 @@ fn list_versions(candidate: &str, call: &EvaluatedCall) -> Result<nu_protocol::P
         ));
     }
     
+    // Versions installed with --from that the API doesn't know about
+    for version in local_versions.iter().filter(|v| !listed.contains(*v)) {
+        let use_marker = if *version == current { ">>>" } else { "" };
+        rows.push(Value::record(
+            Record::from_iter(vec![
+                ("vendor".into(), Value::string("", call.head)),
+                ("use".into(), Value::string(use_marker, call.head)),
+                ("version".into(), Value::string(version, call.head)),
+                ("dist".into(), Value::string("local", call.head)),
+                ("status".into(), Value::string("local only", call.head)),
+                ("identifier".into(), Value::string(version, call.head)),
+            ]),
+            call.head,
+        ));
+    }
+    
     if rows.is_empty() {
         // Fallback if parsing failed or text was empty (though we filtered headers)
         // If text was just headers, rows is empty.
@@ -1,6 +1,6 @@
 mod list;
 mod install;
-mod uninstall;
+pub mod uninstall;  // Public for testing
 mod r#use;
 mod current;
 mod upgrade;
@@ -11,9 +11,22 @@ mod version;
 pub mod env;  // Public for testing
 mod update;
 mod flush;
-mod config;
+pub mod du;  // Public for testing
+mod doctor;
+pub mod config;  // Public for testing
 mod offline;
+mod outdated;
+mod prune;
+mod bundle;
+mod cache;
+mod link;
+mod discover;
+mod init;
+mod exec;
+mod which;
+mod shims;
 mod aliases;
+mod completions;
 
 pub use list::List;
 pub use install::Install;
@@ -28,6 +41,18 @@ pub use version::Version;
 pub use env::Env;
 pub use update::Update;
 pub use flush::Flush;
+pub use du::Du;
+pub use doctor::Doctor;
 pub use config::Config;
 pub use offline::Offline;
+pub use outdated::Outdated;
+pub use prune::Prune;
+pub use bundle::Bundle;
+pub use cache::Cache;
+pub use link::Link;
+pub use discover::Discover;
+pub use init::Init;
+pub use exec::Exec;
+pub use which::Which;
+pub use shims::Shims;
 pub use aliases::{I, Rm, Ls, U, C, Ug, D, H, V};
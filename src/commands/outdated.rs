@@ -0,0 +1,94 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::{api, env, version};
+
+pub struct Outdated;
+
+impl PluginCommand for Outdated {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk outdated"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional("candidate", SyntaxShape::String, "Candidate to check (checks all installed if not specified)")
+            .switch("offline", "Only use the local metadata cache", None)
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Compare installed versions with available ones"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
+        let candidate: Option<String> = call.opt(0)?;
+        let offline = call.has_flag("offline")?;
+
+        let platform = env::detect_platform()
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+
+        let candidates = match candidate {
+            Some(c) => vec![c],
+            None => env::get_installed_candidates(),
+        };
+
+        let mut rows = Vec::new();
+        for candidate in candidates {
+            let mut installed = env::get_installed_versions(&candidate);
+            if installed.is_empty() {
+                continue;
+            }
+            installed.sort_by(|a, b| version::VersionId::parse(a).cmp_version(&version::VersionId::parse(b)));
+
+            let available = if offline {
+                api::get_cached_versions(&candidate, &platform).ok_or_else(|| LabeledError::new(format!(
+                    "No cached versions for {}. Run 'sdk outdated {}' while online first",
+                    candidate, candidate
+                )))?
+            } else {
+                api::get_versions(&candidate, &platform)
+                    .map_err(|e| LabeledError::new(format!("Failed to fetch versions for {}: {}", candidate, e)))?
+            };
+
+            rows.push(outdated_row(&candidate, installed, &available, call));
+        }
+
+        Ok(Value::list(rows, call.head).into_pipeline_data())
+    }
+}
+
+fn outdated_row(candidate: &str, installed: Vec<String>, available: &[api::VersionInfo], call: &EvaluatedCall) -> Value {
+    let current = env::get_current_version(candidate);
+    let latest_same_vendor = current
+        .as_ref()
+        .and_then(|c| version::latest_release(available, Some(&version::VersionId::parse(c).vendor)));
+    let latest_overall = version::latest_release(available, None);
+
+    let optional = |v: Option<String>| v.map_or(Value::nothing(call.head), |v| Value::string(v, call.head));
+
+    Value::record(
+        Record::from_iter(vec![
+            ("candidate".into(), Value::string(candidate, call.head)),
+            ("current".into(), optional(current)),
+            ("latest_same_vendor".into(), optional(latest_same_vendor)),
+            ("latest_overall".into(), optional(latest_overall)),
+            ("installed_versions".into(), Value::list(
+                installed.into_iter().map(|v| Value::string(v, call.head)).collect(),
+                call.head,
+            )),
+        ]),
+        call.head,
+    )
+}
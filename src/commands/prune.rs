@@ -0,0 +1,111 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::env;
+use crate::core::prune::{self, InstalledVersion, PrunePolicy};
+use crate::utils::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+pub struct Prune;
+
+impl PluginCommand for Prune {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk prune"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional("candidate", SyntaxShape::String, "Candidate to prune (prunes all if not specified)")
+            .named("keep", SyntaxShape::Int, "Number of newest versions to keep per candidate", Some('k'))
+            .named("older-than", SyntaxShape::Duration, "Only remove versions installed longer ago than this", None)
+            .named("rc", SyntaxShape::List(Box::new(SyntaxShape::Filepath)), "Additional .sdkmanrc files whose versions are kept", None)
+            .switch("dry-run", "Report what would be removed without deleting anything", Some('n'))
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Remove unused installed versions"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
+        let candidate: Option<String> = call.opt(0)?;
+        let keep: Option<i64> = call.get_flag("keep")?;
+        let older_than: Option<Duration> = call.get_flag("older-than")?;
+        let rc_files: Vec<PathBuf> = call.get_flag::<Vec<String>>("rc")?
+            .unwrap_or_default()
+            .into_iter()
+            .map(PathBuf::from)
+            .chain(env::working_sdkmanrc())
+            .collect();
+        let dry_run = call.has_flag("dry-run")?;
+
+        if keep.is_none() && older_than.is_none() {
+            return Err(LabeledError::new("Specify a pruning policy with --keep and/or --older-than"));
+        }
+        let keep = keep
+            .map(|k| usize::try_from(k).map_err(|_| LabeledError::new("--keep must not be negative")))
+            .transpose()?;
+        let policy = PrunePolicy { keep, older_than };
+
+        let candidates = match candidate {
+            Some(c) => vec![c],
+            None => env::get_installed_candidates(),
+        };
+        let rc_refs: Vec<&std::path::Path> = rc_files.iter().map(|p| p.as_path()).collect();
+
+        let mut removed = Vec::new();
+        let mut reclaimed = 0u64;
+        for candidate in candidates {
+            let installed: Vec<InstalledVersion> = env::get_installed_versions(&candidate)
+                .into_iter()
+                .map(|version| InstalledVersion {
+                    installed_at: env::installed_at(&candidate, &version),
+                    version,
+                })
+                .collect();
+            let protected = prune::protected_versions(&candidate, &rc_refs);
+
+            for version in prune::select_prunable(&installed, &protected, &policy, SystemTime::now()) {
+                let dir = env::candidate_dir(&candidate, &version)
+                    .map_err(|e| LabeledError::new(e.to_string()))?;
+                let size = fs::dir_size(&dir).map(|s| s.bytes).unwrap_or(0);
+
+                if !dry_run {
+                    std::fs::remove_dir_all(&dir)
+                        .map_err(|e| LabeledError::new(format!("Failed to remove {} {}: {}", candidate, version, e)))?;
+                }
+
+                reclaimed += size;
+                removed.push(Value::record(
+                    Record::from_iter(vec![
+                        ("candidate".into(), Value::string(&candidate, call.head)),
+                        ("version".into(), Value::string(version, call.head)),
+                        ("size".into(), Value::filesize(size as i64, call.head)),
+                    ]),
+                    call.head,
+                ));
+            }
+        }
+
+        Ok(Value::record(
+            Record::from_iter(vec![
+                ("dry_run".into(), Value::bool(dry_run, call.head)),
+                ("reclaimed".into(), Value::filesize(reclaimed as i64, call.head)),
+                ("removed".into(), Value::list(removed, call.head)),
+            ]),
+            call.head,
+        ).into_pipeline_data())
+    }
+}
@@ -0,0 +1,79 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::core::shims;
+
+pub struct Shims;
+
+impl PluginCommand for Shims {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk shims"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional("subcommand", SyntaxShape::String, "Subcommand: rehash, or empty to list shims")
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Manage shims that pick the version from .sdkmanrc at run time"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Shims live in ~/.sdkman/shims; put that directory first on PATH. Each shim runs the version \
+        pinned in the nearest .sdkmanrc, or the default version when nothing is pinned."
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        let subcommand: Option<String> = call.opt(0)?;
+        let found = match subcommand.as_deref() {
+            None => shims::list(),
+            Some("rehash") => std::env::current_exe()
+                .map_err(|e| e.into())
+                .and_then(|plugin_binary| shims::rehash(&plugin_binary)),
+            Some(other) => {
+                return Err(LabeledError::new(format!("Unknown subcommand: {} (expected rehash)", other)));
+            }
+        }
+        .map_err(|e| LabeledError::new(e.to_string()))?;
+
+        for shim in found.iter().filter(|shim| !shim.shadowed.is_empty()) {
+            eprintln!(
+                "Warning: {} is also provided by {}; the shim runs {}'s",
+                shim.name,
+                shim.shadowed.join(", "),
+                shim.candidate
+            );
+        }
+
+        let rows = found
+            .into_iter()
+            .map(|shim| {
+                let shadowed = shim.shadowed.into_iter().map(|c| Value::string(c, call.head)).collect();
+                Value::record(
+                    Record::from_iter(vec![
+                        ("name".into(), Value::string(shim.name, call.head)),
+                        ("candidate".into(), Value::string(shim.candidate, call.head)),
+                        ("shadowed".into(), Value::list(shadowed, call.head)),
+                        ("path".into(), Value::string(shim.path.display().to_string(), call.head)),
+                    ]),
+                    call.head,
+                )
+            })
+            .collect();
+
+        Ok(Value::list(rows, call.head).into_pipeline_data())
+    }
+}
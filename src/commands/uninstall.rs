@@ -1,6 +1,10 @@
-use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
+use nu_plugin::{DynamicCompletionCall, EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData, DynamicSuggestion};
+use nu_protocol::engine::ArgType;
 use crate::SdkmanPlugin;
+use crate::commands::completions::{self, VersionCompletion};
+use crate::commands::config::load_plugin_config;
+use crate::constants;
 use crate::core::env;
 
 pub struct Uninstall;
@@ -15,46 +19,142 @@ impl PluginCommand for Uninstall {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .required("candidate", SyntaxShape::String, "Candidate to uninstall")
-            .required("version", SyntaxShape::String, "Version to uninstall")
+            .rest("versions", SyntaxShape::String, "Versions to uninstall")
+            .switch("all", "Uninstall every installed version of the candidate", Some('a'))
+            .switch("force", "Allow removing the current version (unlinks it)", Some('f'))
+            .named("switch-to", SyntaxShape::String, "Version to make current before removing the current one", Some('s'))
             .category(Category::Custom("sdk".into()))
     }
 
     fn description(&self) -> &str {
-        "Uninstall a candidate version"
+        "Uninstall candidate versions"
     }
 
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let candidate: String = call.req(0)?;
-        let version: String = call.req(1)?;
+        let requested: Vec<String> = call.rest(1)?;
+        let all = call.has_flag("all")?;
+        let force = call.has_flag("force")?;
+        let switch_to: Option<String> = call.get_flag("switch-to")?;
         
-        if !env::is_installed(&candidate, &version) {
-            return Err(LabeledError::new(format!(
-                "{} {} is not installed",
-                candidate, version
-            )));
-        }
+        let versions = match (all, requested.is_empty()) {
+            (true, true) => env::get_installed_versions(&candidate),
+            (false, false) => requested,
+            (true, false) => return Err(LabeledError::new("Pass either versions or --all, not both")),
+            (false, true) => return Err(LabeledError::new("Specify at least one version to uninstall, or --all")),
+        };
         
-        let install_dir = env::candidate_dir(&candidate, &version)
-            .map_err(|e| LabeledError::new(e.to_string()))?;
-        std::fs::remove_dir_all(&install_dir)
-            .map_err(|e| LabeledError::new(format!("Failed to remove installation: {}", e)))?;
+        if versions.is_empty() {
+            return Err(LabeledError::new(format!("No {} versions are installed", candidate)));
+        }
         
-        let current = env::get_current_version(&candidate);
-        if current.as_ref() == Some(&version) {
-            if let Ok(current_link) = env::candidate_current(&candidate) {
-                std::fs::remove_dir_all(&current_link).ok();
+        for version in &versions {
+            if !env::is_installed(&candidate, version) {
+                return Err(LabeledError::new(format!(
+                    "{} {} is not installed",
+                    candidate, version
+                )));
             }
         }
         
-        Ok(Value::string(
-            format!("{} {} uninstalled successfully", candidate, version),
-            call.head,
-        ).into_pipeline_data())
+        let mut messages = Vec::new();
+        release_current(&candidate, &versions, switch_to.as_deref(), force, &mut messages)?;
+        
+        for version in &versions {
+            let install_dir = env::candidate_dir(&candidate, version)
+                .map_err(|e| LabeledError::new(e.to_string()))?;
+            std::fs::remove_dir_all(&install_dir)
+                .map_err(|e| LabeledError::new(format!("Failed to remove {} {}: {}", candidate, version, e)))?;
+            messages.push(format!("{} {} uninstalled successfully", candidate, version));
+        }
+        
+        Ok(Value::string(messages.join("\n"), call.head).into_pipeline_data())
+    }
+
+    #[allow(deprecated)]
+    fn get_dynamic_completion(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: DynamicCompletionCall,
+        arg_type: ArgType,
+        _experimental: nu_protocol::engine::ExperimentalMarker,
+    ) -> Option<Vec<DynamicSuggestion>> {
+        completions::complete_candidate_version(engine, &call, &arg_type, VersionCompletion::Installed)
+    }
+}
+
+/// Makes sure no global or local `current` link is left pointing at a removed version.
+///
+/// Without `--switch-to` or `--force` this refuses, so the candidate is never
+/// silently left without an active version.
+pub fn release_current(
+    candidate: &str,
+    versions: &[String],
+    switch_to: Option<&str>,
+    force: bool,
+    messages: &mut Vec<String>,
+) -> Result<(), LabeledError> {
+    let removing = |v: &Option<String>| v.as_ref().is_some_and(|v| versions.contains(v));
+    let global = env::get_current_version(candidate);
+    let local = env::get_local_current_version(candidate);
+    
+    if !removing(&global) && !removing(&local) {
+        return Ok(());
+    }
+    
+    if let Some(target) = switch_to {
+        if versions.iter().any(|v| v == target) {
+            return Err(LabeledError::new(format!("Cannot switch to {} {}: it is being uninstalled", candidate, target)));
+        }
+        if !env::is_installed(candidate, target) {
+            return Err(LabeledError::new(format!("{} {} is not installed", candidate, target)));
+        }
+        if removing(&global) {
+            env::set_current_version(candidate, target)
+                .map_err(|e| LabeledError::new(format!("Failed to set current version: {}", e)))?;
+            messages.push(format!("Switched {} to {}", candidate, target));
+        }
+        if removing(&local) {
+            env::set_local_current_version(candidate, target)
+                .map_err(|e| LabeledError::new(format!("Failed to set local current version: {}", e)))?;
+            messages.push(format!("Switched local {} to {}", candidate, target));
+        }
+        return Ok(());
+    }
+    
+    if !force {
+        let (version, scope) = if removing(&global) {
+            (global, "the current version")
+        } else {
+            (local, "linked by the local .sdkman environment")
+        };
+        return Err(LabeledError::new(format!(
+            "{} {} is {}. Use --switch-to <version> to select another version, or --force to remove it anyway",
+            candidate, version.unwrap_or_default(), scope
+        )));
+    }
+    
+    if removing(&global) {
+        if let Ok(current_link) = env::candidate_current(candidate) {
+            std::fs::remove_dir_all(&current_link).ok();
+        }
+        messages.push(format!("Unlinked current {} version", candidate));
+    }
+    if removing(&local) {
+        if let Some(local_dir) = env::local_sdkman_dir() {
+            let local_current = local_dir.join(constants::CANDIDATES_DIR).join(candidate).join(constants::CURRENT_LINK);
+            std::fs::remove_dir_all(&local_current).ok();
+        }
+        messages.push(format!("Unlinked local {} version", candidate));
     }
+    Ok(())
 }
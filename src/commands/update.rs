@@ -1,7 +1,9 @@
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
 use nu_protocol::{Category, LabeledError, Signature, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
-use crate::core::api;
+use crate::commands::config::load_plugin_config;
+use crate::constants;
+use crate::core::{api, config, env};
 
 pub struct Update;
 
@@ -24,13 +26,21 @@ impl PluginCommand for Update {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let candidates = api::get_candidates()
             .map_err(|e| LabeledError::new(format!("Failed to fetch candidates: {}", e)))?;
         
+        // Keep var/platform in sync for the bash CLI sharing this directory
+        let platform = env::detect_platform()
+            .map_err(|e| LabeledError::new(e.to_string()))?;
+        config::write_var(constants::PLATFORM_FILE, &platform)
+            .map_err(|e| LabeledError::new(format!("Failed to write platform: {}", e)))?;
+        
         Ok(Value::string(
             format!("Candidate cache updated. {} candidates available.", candidates.len()),
             call.head,
@@ -1,7 +1,9 @@
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
 use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
 use crate::core::{api, env, install};
+use crate::core::version::{self, UpgradePolicy};
 
 pub struct Upgrade;
 
@@ -15,6 +17,9 @@ impl PluginCommand for Upgrade {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .optional("candidate", SyntaxShape::String, "Candidate to upgrade (upgrades all if not specified)")
+            .named("within", SyntaxShape::String, "Upgrade scope: major, minor or any (default: major for java, any otherwise)", Some('w'))
+            .switch("any-vendor", "Allow switching to a different vendor", None)
+            .switch("lts", "Only upgrade to LTS releases", None)
             .category(Category::Custom("sdk".into()))
     }
 
@@ -25,40 +30,59 @@ impl PluginCommand for Upgrade {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let candidate: Option<String> = call.opt(0)?;
+        let policy = upgrade_policy(call)?;
         
         if let Some(candidate) = candidate {
-            upgrade_candidate(&candidate, call)
+            upgrade_candidate(&candidate, &policy, call)
         } else {
-            upgrade_all(call)
+            upgrade_all(&policy, call)
         }
     }
 }
 
-fn upgrade_candidate(candidate: &str, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
-    let current = env::get_current_version(candidate);
+fn upgrade_policy(call: &EvaluatedCall) -> Result<UpgradePolicy, LabeledError> {
+    let within: Option<String> = call.get_flag("within")?;
+    let within = within
+        .map(|w| w.parse().map_err(LabeledError::new))
+        .transpose()?;
+    
+    Ok(UpgradePolicy {
+        within,
+        same_vendor: !call.has_flag("any-vendor")?,
+        lts_only: call.has_flag("lts")?,
+    })
+}
+
+fn upgrade_candidate(candidate: &str, policy: &UpgradePolicy, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+    let current = env::get_current_version(candidate)
+        .ok_or_else(|| LabeledError::new(format!("No {} version currently in use", candidate)))?;
     
-    if current.is_none() {
-        return Err(LabeledError::new(format!("No {} version currently in use", candidate)));
+    if env::is_local_version(candidate, &current) {
+        return Ok(Value::string(
+            format!("{} {} is a local version and is not upgraded", candidate, current),
+            call.head,
+        ).into_pipeline_data());
     }
     
     let platform = env::detect_platform()
         .map_err(|e| LabeledError::new(e.to_string()))?;
-    let latest = api::get_default_version(candidate, &platform)
-        .map_err(|e| LabeledError::new(format!("Failed to get latest version: {}", e)))?;
+    let available = api::get_versions(candidate, &platform)
+        .map_err(|e| LabeledError::new(format!("Failed to get available versions: {}", e)))?;
     
-    let current = current.unwrap();
-    
-    if current == latest {
-        return Ok(Value::string(
-            format!("{} is already at the latest version ({})", candidate, latest),
+    let latest = match version::select_upgrade(candidate, &current, &available, policy) {
+        Some(latest) => latest,
+        None => return Ok(Value::string(
+            format!("{} is already at the latest version ({})", candidate, current),
             call.head,
-        ).into_pipeline_data());
-    }
+        ).into_pipeline_data()),
+    };
     
     if env::is_installed(candidate, &latest) {
         env::set_current_version(candidate, &latest)
@@ -70,19 +94,21 @@ fn upgrade_candidate(candidate: &str, call: &EvaluatedCall) -> Result<nu_protoco
         ).into_pipeline_data());
     }
     
-    install::install_candidate(candidate, &latest, &platform)
+    let hooks = install::install_candidate(candidate, &latest, &platform)
         .map_err(|e| LabeledError::new(format!("Upgrade failed: {}", e)))?;
     
     env::set_current_version(candidate, &latest)
         .map_err(|e| LabeledError::new(format!("Failed to set current version: {}", e)))?;
     
-    Ok(Value::string(
-        format!("Upgraded {} from {} to {}", candidate, current, latest),
-        call.head,
-    ).into_pipeline_data())
+    let mut message = format!("Upgraded {} from {} to {}", candidate, current, latest);
+    for hook in hooks {
+        message.push_str(&format!("\n{}", hook));
+    }
+    
+    Ok(Value::string(message, call.head).into_pipeline_data())
 }
 
-fn upgrade_all(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
+fn upgrade_all(policy: &UpgradePolicy, call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, LabeledError> {
     let candidates_dir = env::candidates_dir()
         .map_err(|e| LabeledError::new(e.to_string()))?;
     
@@ -97,7 +123,7 @@ fn upgrade_all(call: &EvaluatedCall) -> Result<nu_protocol::PipelineData, Labele
             if entry.path().is_dir() {
                 let candidate = entry.file_name().to_string_lossy().to_string();
                 if env::get_current_version(&candidate).is_some() {
-                    match upgrade_candidate(&candidate, call) {
+                    match upgrade_candidate(&candidate, policy, call) {
                         Ok(pd) => {
                             if let Ok(v) = pd.into_value(call.head) {
                                 results.push(v);
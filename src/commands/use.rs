@@ -1,6 +1,9 @@
-use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData};
+use nu_plugin::{DynamicCompletionCall, EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Signature, SyntaxShape, Value, IntoPipelineData, DynamicSuggestion};
+use nu_protocol::engine::ArgType;
 use crate::SdkmanPlugin;
+use crate::commands::completions::{self, VersionCompletion};
+use crate::commands::config::load_plugin_config;
 use crate::core::env;
 
 pub struct Use;
@@ -26,10 +29,12 @@ impl PluginCommand for Use {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+        
         let candidate: String = call.req(0)?;
         let version: String = call.req(1)?;
         
@@ -52,4 +57,16 @@ impl PluginCommand for Use {
         
         Ok(Value::string(message, call.head).into_pipeline_data())
     }
+
+    #[allow(deprecated)]
+    fn get_dynamic_completion(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: DynamicCompletionCall,
+        arg_type: ArgType,
+        _experimental: nu_protocol::engine::ExperimentalMarker,
+    ) -> Option<Vec<DynamicSuggestion>> {
+        completions::complete_candidate_version(engine, &call, &arg_type, VersionCompletion::InProject)
+    }
 }
@@ -1,6 +1,9 @@
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand, Plugin};
 use nu_protocol::{Category, LabeledError, Signature, Value, IntoPipelineData};
 use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::constants;
+use crate::core::config;
 
 pub struct Version;
 
@@ -23,13 +26,19 @@ impl PluginCommand for Version {
     fn run(
         &self,
         plugin: &Self::Plugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         _input: nu_protocol::PipelineData,
     ) -> Result<nu_protocol::PipelineData, LabeledError> {
-        Ok(Value::string(
-            format!("SDKMAN! Nushell Plugin {}", plugin.version()),
-            call.head,
-        ).into_pipeline_data())
+        load_plugin_config(engine)?;
+        
+        let mut message = format!("SDKMAN! Nushell Plugin {}", plugin.version());
+        
+        // Report the bash CLI version when it shares this SDKMAN directory
+        if let Some(cli_version) = config::read_var(constants::VERSION_FILE) {
+            message.push_str(&format!("\nSDKMAN! CLI {}", cli_version));
+        }
+        
+        Ok(Value::string(message, call.head).into_pipeline_data())
     }
 }
@@ -0,0 +1,72 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Category, LabeledError, Record, Signature, Span, SyntaxShape, Value, IntoPipelineData};
+use crate::SdkmanPlugin;
+use crate::commands::config::load_plugin_config;
+use crate::commands::exec::string_env;
+use crate::core::env;
+use crate::core::which::{self, BinaryLocation};
+use std::path::PathBuf;
+
+pub struct Which;
+
+impl PluginCommand for Which {
+    type Plugin = SdkmanPlugin;
+
+    fn name(&self) -> &str {
+        "sdk which"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required("binary", SyntaxShape::String, "Executable name (e.g. java)")
+            .switch("all", "List every copy, including shadowed ones", Some('a'))
+            .category(Category::Custom("sdk".into()))
+    }
+
+    fn description(&self) -> &str {
+        "Show which candidate and version a binary on PATH belongs to"
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: nu_protocol::PipelineData,
+    ) -> Result<nu_protocol::PipelineData, LabeledError> {
+        load_plugin_config(engine)?;
+
+        let binary: String = call.req(0)?;
+        // The project's .sdkman sits next to its .sdkmanrc, also from subdirectories
+        let cwd = env::project_root(&PathBuf::from(engine.get_current_dir()?));
+        let env = string_env(engine.get_env_vars()?);
+        let path = env.iter().find(|(k, _)| k.eq_ignore_ascii_case("PATH")).map(|(_, v)| v.as_str());
+
+        let found = which::which(&binary, path, &cwd);
+        let span = call.head;
+
+        if call.has_flag("all")? {
+            let rows = found.iter().map(|l| location_record(l, span)).collect();
+            return Ok(Value::list(rows, span).into_pipeline_data());
+        }
+
+        let first = found
+            .first()
+            .ok_or_else(|| LabeledError::new(format!("{} not found on PATH or in any candidate", binary)))?;
+        Ok(location_record(first, span).into_pipeline_data())
+    }
+}
+
+fn location_record(location: &BinaryLocation, span: Span) -> Value {
+    let optional = |s: &Option<String>| s.as_ref().map_or(Value::nothing(span), |s| Value::string(s, span));
+    Value::record(
+        Record::from_iter(vec![
+            ("binary".into(), Value::string(&location.binary, span)),
+            ("path".into(), Value::string(location.path.display().to_string(), span)),
+            ("candidate".into(), optional(&location.candidate)),
+            ("version".into(), optional(&location.version)),
+            ("source".into(), Value::string(location.source.to_string(), span)),
+        ]),
+        span,
+    )
+}
@@ -1,4 +1,4 @@
-/// Constants for SDKMAN directory structure and configuration.
+// Constants for SDKMAN directory structure and configuration.
 
 // SDKMAN directory structure
 pub const SDKMAN_DIR_NAME: &str = ".sdkman";
@@ -8,7 +8,10 @@ pub const CURRENT_LINK: &str = "current";
 pub const TMP_DIR: &str = "tmp";
 pub const VAR_DIR: &str = "var";
 pub const ETC_DIR: &str = "etc";
+pub const HOOKS_DIR: &str = "hooks";
 pub const METADATA_DIR: &str = "metadata";
+pub const ARCHIVES_DIR: &str = "archives";
+pub const SHIMS_DIR: &str = "shims";
 
 // Environment variables
 pub const ENV_SDKMAN_DIR: &str = "SDKMAN_DIR";
@@ -17,11 +20,27 @@ pub const ENV_PWD: &str = "PWD";
 // Windows-specific
 pub const VERSION_MARKER: &str = ".version";
 
+// Records where a version installed with `sdk install --from` came from
+pub const LOCAL_SOURCE_MARKER: &str = ".sdkman-source";
+
 // Activation scripts
 pub const ENV_SCRIPT_NU: &str = "env.nu";
 pub const ENV_SCRIPT_SH: &str = "env.sh";
+pub const ENV_SCRIPT_BASH: &str = "env.bash";
+pub const ENV_SCRIPT_ZSH: &str = "env.zsh";
 pub const ENV_SCRIPT_FISH: &str = "env.fish";
+pub const ENV_SCRIPT_PWSH: &str = "env.ps1";
+pub const ENV_SCRIPT_XONSH: &str = "env.xsh";
+pub const ENV_SCRIPT_ELVISH: &str = "env.elv";
+
+// Global init module
+pub const BIN_DIR: &str = "bin";
+pub const INIT_SCRIPT_NU: &str = "sdkman-init.nu";
 
 // Config files
 pub const CONFIG_FILE: &str = "config";
 pub const VERSION_FILE: &str = "version";
+
+// State files in var/ shared with bash SDKMAN
+pub const CANDIDATES_FILE: &str = "candidates";
+pub const PLATFORM_FILE: &str = "platform";
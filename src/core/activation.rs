@@ -0,0 +1,373 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use crate::constants;
+use crate::core::config::Config;
+use crate::core::env;
+
+/// Name of the function every activation script defines to undo itself.
+pub const DEACTIVATE_FN: &str = "sdkman_deactivate";
+
+/// Shells activation scripts can be rendered for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Nu,
+    Sh,
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+    Xonsh,
+    Elvish,
+}
+
+impl Shell {
+    pub const ALL: [Shell; 8] = [
+        Shell::Nu,
+        Shell::Sh,
+        Shell::Bash,
+        Shell::Zsh,
+        Shell::Fish,
+        Shell::PowerShell,
+        Shell::Xonsh,
+        Shell::Elvish,
+    ];
+
+    /// File name of the script inside a `.sdkman` directory.
+    pub fn script_name(self) -> &'static str {
+        match self {
+            Shell::Nu => constants::ENV_SCRIPT_NU,
+            Shell::Sh => constants::ENV_SCRIPT_SH,
+            Shell::Bash => constants::ENV_SCRIPT_BASH,
+            Shell::Zsh => constants::ENV_SCRIPT_ZSH,
+            Shell::Fish => constants::ENV_SCRIPT_FISH,
+            Shell::PowerShell => constants::ENV_SCRIPT_PWSH,
+            Shell::Xonsh => constants::ENV_SCRIPT_XONSH,
+            Shell::Elvish => constants::ENV_SCRIPT_ELVISH,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Nu => "Nushell",
+            Shell::Sh => "sh",
+            Shell::Bash => "Bash",
+            Shell::Zsh => "Zsh",
+            Shell::Fish => "Fish",
+            Shell::PowerShell => "PowerShell",
+            Shell::Xonsh => "Xonsh",
+            Shell::Elvish => "Elvish",
+        }
+    }
+
+    /// Command activating the script at `script` (relative to the project).
+    pub fn activate_command(self, script: &str) -> String {
+        match self {
+            Shell::Nu => format!("overlay use {}", script),
+            Shell::Sh => format!(". {}", script),
+            Shell::Bash | Shell::Zsh | Shell::Fish | Shell::Xonsh => format!("source {}", script),
+            Shell::PowerShell => format!(". {}", script.replace('/', "\\")),
+            Shell::Elvish => format!("eval (slurp < {})", script),
+        }
+    }
+}
+
+/// Environment changes applied by an activation script.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Activation {
+    /// Directories prepended to PATH, highest priority first.
+    pub path_entries: Vec<PathBuf>,
+    /// Variables set on activation, e.g. `JAVA_HOME`.
+    pub vars: Vec<(String, String)>,
+}
+
+impl Activation {
+    /// Builds the activation for the `current` links of candidates in a
+    /// `candidates` directory: their `bin` on PATH and their home variable.
+    pub fn for_candidates(candidates_dir: &Path, candidates: &[String]) -> Self {
+        let homes: Vec<(String, PathBuf)> = candidates
+            .iter()
+            .map(|candidate| (candidate.clone(), candidates_dir.join(candidate).join(constants::CURRENT_LINK)))
+            .collect();
+        Activation::for_homes(&homes)
+    }
+
+    /// Builds the activation for candidate home directories: `<home>/bin`
+    /// on PATH and the candidate's home variable pointing at `<home>`.
+    pub fn for_homes(homes: &[(String, PathBuf)]) -> Self {
+        let config = Config::load();
+        let mut activation = Activation::default();
+        for (candidate, home) in homes {
+            activation.path_entries.push(home.join("bin"));
+            activation.vars.push((config.home_var(candidate), home.display().to_string()));
+        }
+        activation
+    }
+
+    /// Renders the activation script for a shell.
+    ///
+    /// Scripts save PATH and the variables they overwrite, and define
+    /// `sdkman_deactivate` which restores them. Activating twice first
+    /// deactivates the previous activation.
+    pub fn render(&self, shell: Shell) -> String {
+        let mut out = String::from("# SDKMAN local environment, generated by `sdk env init`\n");
+        out.push_str(&format!("# Activate: {}\n", shell.activate_command(&format!("{}/{}", constants::SDKMAN_DIR_NAME, shell.script_name()))));
+        out.push_str(&format!("# Deactivate: {}\n\n", DEACTIVATE_FN));
+        match shell {
+            Shell::Nu => self.render_nu(&mut out),
+            Shell::Sh | Shell::Bash | Shell::Zsh => self.render_posix(&mut out),
+            Shell::Fish => self.render_fish(&mut out),
+            Shell::PowerShell => self.render_pwsh(&mut out),
+            Shell::Xonsh => self.render_xonsh(&mut out),
+            Shell::Elvish => self.render_elvish(&mut out),
+        }
+        out
+    }
+
+    fn paths(&self) -> Vec<String> {
+        self.path_entries.iter().map(|p| p.display().to_string()).collect()
+    }
+
+    fn render_nu(&self, out: &mut String) {
+        out.push_str("export-env {\n");
+        out.push_str("    $env._SDKMAN_OLD = ($env._SDKMAN_OLD? | default {\n");
+        out.push_str("        PATH: $env.PATH\n");
+        let saved: Vec<String> = self.vars.iter().map(|(name, _)| format!("{}: $env.{}?", name, name)).collect();
+        out.push_str(&format!("        vars: {{{}}}\n", saved.join(", ")));
+        out.push_str("    })\n");
+        let paths: Vec<String> = self.paths().iter().map(|p| nu_quote(p)).collect();
+        out.push_str(&format!(
+            "    $env.PATH = ($env._SDKMAN_OLD.PATH | split row (char esep) | prepend [{}] | uniq)\n",
+            paths.join(" ")
+        ));
+        for (name, value) in &self.vars {
+            out.push_str(&format!("    $env.{} = {}\n", name, nu_quote(value)));
+        }
+        out.push_str("}\n\n");
+
+        out.push_str(&format!("export def --env {} [] {{\n", DEACTIVATE_FN));
+        out.push_str("    if '_SDKMAN_OLD' not-in $env { return }\n");
+        out.push_str("    let old = $env._SDKMAN_OLD\n");
+        out.push_str("    $env.PATH = $old.PATH\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!(
+                "    if $old.vars.{name} == null {{ hide-env -i {name} }} else {{ $env.{name} = $old.vars.{name} }}\n",
+            ));
+        }
+        out.push_str("    hide-env _SDKMAN_OLD\n");
+        out.push_str("}\n");
+    }
+
+    fn render_posix(&self, out: &mut String) {
+        out.push_str(&format!("{}() {{\n", DEACTIVATE_FN));
+        out.push_str("    [ -n \"${_SDKMAN_OLD_PATH+x}\" ] || return 0\n");
+        out.push_str("    PATH=\"$_SDKMAN_OLD_PATH\"\n");
+        out.push_str("    export PATH\n");
+        out.push_str("    unset _SDKMAN_OLD_PATH\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("    if [ -n \"${{_SDKMAN_OLD_{name}+x}}\" ]; then\n"));
+            out.push_str(&format!("        {name}=\"$_SDKMAN_OLD_{name}\"\n"));
+            out.push_str(&format!("        export {name}\n"));
+            out.push_str(&format!("        unset _SDKMAN_OLD_{name}\n"));
+            out.push_str("    else\n");
+            out.push_str(&format!("        unset {name}\n"));
+            out.push_str("    fi\n");
+        }
+        out.push_str("}\n\n");
+
+        out.push_str(&format!("{}\n\n", DEACTIVATE_FN));
+        out.push_str("_SDKMAN_OLD_PATH=\"$PATH\"\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("if [ -n \"${{{name}+x}}\" ]; then _SDKMAN_OLD_{name}=\"${name}\"; fi\n"));
+        }
+        for path in self.paths().iter().rev() {
+            out.push_str(&format!("PATH={}:\"$PATH\"\n", sh_quote(path)));
+        }
+        out.push_str("export PATH\n");
+        for (name, value) in &self.vars {
+            out.push_str(&format!("{}={}\n", name, sh_quote(value)));
+            out.push_str(&format!("export {}\n", name));
+        }
+    }
+
+    fn render_fish(&self, out: &mut String) {
+        out.push_str(&format!("function {}\n", DEACTIVATE_FN));
+        out.push_str("    set -q _SDKMAN_OLD_PATH; or return 0\n");
+        out.push_str("    set -gx PATH $_SDKMAN_OLD_PATH\n");
+        out.push_str("    set -e _SDKMAN_OLD_PATH\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("    if set -q _SDKMAN_OLD_{name}\n"));
+            out.push_str(&format!("        set -gx {name} $_SDKMAN_OLD_{name}\n"));
+            out.push_str(&format!("        set -e _SDKMAN_OLD_{name}\n"));
+            out.push_str("    else\n");
+            out.push_str(&format!("        set -e {name}\n"));
+            out.push_str("    end\n");
+        }
+        out.push_str("end\n\n");
+
+        out.push_str(&format!("{}\n\n", DEACTIVATE_FN));
+        out.push_str("set -gx _SDKMAN_OLD_PATH $PATH\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("set -q {name}; and set -gx _SDKMAN_OLD_{name} ${name}\n"));
+        }
+        let paths: Vec<String> = self.paths().iter().map(|p| fish_quote(p)).collect();
+        out.push_str(&format!("set -gx PATH {} $PATH\n", paths.join(" ")));
+        for (name, value) in &self.vars {
+            out.push_str(&format!("set -gx {} {}\n", name, fish_quote(value)));
+        }
+    }
+
+    fn render_pwsh(&self, out: &mut String) {
+        out.push_str(&format!("function global:{} {{\n", DEACTIVATE_FN));
+        out.push_str("    if (-not (Test-Path Env:_SDKMAN_OLD_PATH)) { return }\n");
+        out.push_str("    $env:PATH = $env:_SDKMAN_OLD_PATH\n");
+        out.push_str("    Remove-Item Env:_SDKMAN_OLD_PATH\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("    if (Test-Path Env:_SDKMAN_OLD_{name}) {{\n"));
+            out.push_str(&format!("        $env:{name} = $env:_SDKMAN_OLD_{name}\n"));
+            out.push_str(&format!("        Remove-Item Env:_SDKMAN_OLD_{name}\n"));
+            out.push_str("    } else {\n");
+            out.push_str(&format!("        Remove-Item Env:{name} -ErrorAction SilentlyContinue\n"));
+            out.push_str("    }\n");
+        }
+        out.push_str("}\n\n");
+
+        out.push_str(&format!("{}\n\n", DEACTIVATE_FN));
+        out.push_str("$env:_SDKMAN_OLD_PATH = $env:PATH\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("if (Test-Path Env:{name}) {{ $env:_SDKMAN_OLD_{name} = $env:{name} }}\n"));
+        }
+        let mut parts: Vec<String> = self.paths().iter().map(|p| pwsh_quote(p)).collect();
+        parts.push("$env:PATH".into());
+        out.push_str(&format!("$env:PATH = {}\n", parts.join(" + [IO.Path]::PathSeparator + ")));
+        for (name, value) in &self.vars {
+            out.push_str(&format!("$env:{} = {}\n", name, pwsh_quote(value)));
+        }
+    }
+
+    fn render_xonsh(&self, out: &mut String) {
+        out.push_str(&format!("def {}():\n", DEACTIVATE_FN));
+        out.push_str("    global _sdkman_old\n");
+        out.push_str("    if '_sdkman_old' not in globals():\n");
+        out.push_str("        return\n");
+        out.push_str("    $PATH = _sdkman_old['PATH']\n");
+        out.push_str("    for name, value in _sdkman_old['vars'].items():\n");
+        out.push_str("        if value is None:\n");
+        out.push_str("            ${...}.pop(name, None)\n");
+        out.push_str("        else:\n");
+        out.push_str("            ${...}[name] = value\n");
+        out.push_str("    del _sdkman_old\n\n");
+
+        out.push_str(&format!("{}()\n\n", DEACTIVATE_FN));
+        let saved: Vec<String> = self.vars.iter().map(|(name, _)| format!("{0}: ${{...}}.get({0})", py_quote(name))).collect();
+        out.push_str(&format!("_sdkman_old = {{'PATH': list($PATH), 'vars': {{{}}}}}\n", saved.join(", ")));
+        let paths: Vec<String> = self.paths().iter().map(|p| py_quote(p)).collect();
+        out.push_str(&format!("$PATH = [{}] + list($PATH)\n", paths.join(", ")));
+        for (name, value) in &self.vars {
+            out.push_str(&format!("${{...}}[{}] = {}\n", py_quote(name), py_quote(value)));
+        }
+    }
+
+    fn render_elvish(&self, out: &mut String) {
+        out.push_str(&format!("fn {} {{\n", DEACTIVATE_FN));
+        out.push_str("    if (not (has-env _SDKMAN_OLD_PATH)) { return }\n");
+        out.push_str("    set-env PATH (get-env _SDKMAN_OLD_PATH)\n");
+        out.push_str("    unset-env _SDKMAN_OLD_PATH\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("    if (has-env _SDKMAN_OLD_{name}) {{\n"));
+            out.push_str(&format!("        set-env {name} (get-env _SDKMAN_OLD_{name})\n"));
+            out.push_str(&format!("        unset-env _SDKMAN_OLD_{name}\n"));
+            out.push_str("    } else {\n");
+            out.push_str(&format!("        unset-env {name}\n"));
+            out.push_str("    }\n");
+        }
+        out.push_str("}\n");
+        // Functions defined inside `eval` don't outlive it; export to the REPL
+        out.push_str(&format!("edit:add-var {0}~ ${0}~\n\n", DEACTIVATE_FN));
+
+        out.push_str(&format!("{}\n\n", DEACTIVATE_FN));
+        out.push_str("set-env _SDKMAN_OLD_PATH (get-env PATH)\n");
+        for (name, _) in &self.vars {
+            out.push_str(&format!("if (has-env {name}) {{ set-env _SDKMAN_OLD_{name} (get-env {name}) }}\n"));
+        }
+        let paths: Vec<String> = self.paths().iter().map(|p| elvish_quote(p)).collect();
+        out.push_str(&format!("set paths = [{} $@paths]\n", paths.join(" ")));
+        for (name, value) in &self.vars {
+            out.push_str(&format!("set-env {} {}\n", name, elvish_quote(value)));
+        }
+    }
+}
+
+/// Candidates activated in a project: those pinned in `.sdkmanrc` and
+/// those linked in its `.sdkman/candidates` directory, sorted.
+pub fn local_candidates(project_dir: &Path) -> Vec<String> {
+    let mut candidates: Vec<String> = env::read_sdkmanrc(&project_dir.join(constants::SDKMAN_RC_FILE))
+        .map(|pins| pins.into_keys().collect())
+        .unwrap_or_default();
+    let linked_dir = project_dir.join(constants::SDKMAN_DIR_NAME).join(constants::CANDIDATES_DIR);
+    if let Ok(entries) = std::fs::read_dir(linked_dir) {
+        candidates.extend(
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().into_owned()),
+        );
+    }
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+/// Writes the activation scripts of every shell to the project's `.sdkman`
+/// directory.
+///
+/// # Returns
+/// Paths of the written scripts
+///
+/// # Errors
+/// Returns error if a script cannot be written
+pub fn write_local_scripts(project_dir: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let local_dir = project_dir.join(constants::SDKMAN_DIR_NAME);
+    std::fs::create_dir_all(&local_dir)?;
+    let activation = Activation::for_candidates(&local_dir.join(constants::CANDIDATES_DIR), &local_candidates(project_dir));
+
+    let mut written = Vec::new();
+    for shell in Shell::ALL {
+        let path = local_dir.join(shell.script_name());
+        std::fs::write(&path, activation.render(shell))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Rewrites the project's activation scripts if it has any, so candidates
+/// linked after `sdk env init` are activated too.
+pub fn refresh_local_scripts(project_dir: &Path) {
+    let local_dir = project_dir.join(constants::SDKMAN_DIR_NAME);
+    if Shell::ALL.iter().any(|shell| local_dir.join(shell.script_name()).exists()) {
+        write_local_scripts(project_dir).ok();
+    }
+}
+
+fn nu_quote(s: &str) -> String {
+    format!("r#'{}'#", s)
+}
+
+fn sh_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn pwsh_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn py_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn elvish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
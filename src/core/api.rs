@@ -1,6 +1,8 @@
 use std::error::Error;
-
-const API_BASE: &str = "https://api.sdkman.io/2";
+use std::path::PathBuf;
+use crate::constants;
+use crate::core::{config, env};
+use crate::utils::download;
 
 /// Information about a candidate version from the SDKMAN API.
 #[derive(Debug, Clone)]
@@ -10,19 +12,41 @@ pub struct VersionInfo {
     pub default: bool,
 }
 
+fn api_base() -> String {
+    config::Config::load().api_base_url
+}
+
+/// Performs a GET request against the SDKMAN API and returns the body.
+///
+/// Fails immediately in offline mode so callers fall back to their caches.
+fn fetch(url: &str) -> Result<String, Box<dyn Error>> {
+    if config::Config::load().offline {
+        return Err("Offline mode is enabled".into());
+    }
+    let response = download::api_client()?.get(url).send()?.error_for_status()?;
+    Ok(response.text()?)
+}
+
 /// Fetches the list of all available candidates from the SDKMAN API.
 ///
+/// The list is stored in `~/.sdkman/var/candidates`, the cache bash SDKMAN
+/// uses, and read back from there if the request fails.
+///
 /// # Returns
 /// Vector of candidate names (e.g., ["java", "gradle", "maven"])
 ///
 /// # Errors
-/// Returns error if network request fails or response cannot be parsed
+/// Returns error if network request fails and no cached list exists
 pub fn get_candidates() -> Result<Vec<String>, Box<dyn Error>> {
-    let url = format!("{}/candidates/all", API_BASE);
-    let response = reqwest::blocking::get(&url)?;
-    let text = response.text()?;
-    let candidates: Vec<String> = text.split(',').map(|s| s.trim().to_string()).collect();
-    Ok(candidates)
+    let url = format!("{}/candidates/all", api_base());
+    match fetch(&url) {
+        Ok(text) => {
+            let candidates: Vec<String> = text.split(',').map(|s| s.trim().to_string()).collect();
+            config::write_cached_candidates(&candidates).ok();
+            Ok(candidates)
+        }
+        Err(e) => config::cached_candidates().ok_or(e),
+    }
 }
 
 /// Fetches the formatted candidates list from the SDKMAN API.
@@ -32,9 +56,8 @@ pub fn get_candidates() -> Result<Vec<String>, Box<dyn Error>> {
 /// # Errors
 /// Returns error if network request fails
 pub fn get_candidates_list() -> Result<String, Box<dyn Error>> {
-    let url = format!("{}/candidates/list", API_BASE);
-    let response = reqwest::blocking::get(&url)?;
-    Ok(response.text()?)
+    let url = format!("{}/candidates/list", api_base());
+    fetch(&url)
 }
 
 /// Fetches the formatted versions list for a candidate from the SDKMAN API.
@@ -52,10 +75,9 @@ pub fn get_candidates_list() -> Result<String, Box<dyn Error>> {
 pub fn get_versions_list(candidate: &str, platform: &str, current: &str, installed: &str) -> Result<String, Box<dyn Error>> {
     let url = format!(
         "{}/candidates/{}/{}/versions/list?current={}&installed={}",
-        API_BASE, candidate, platform, current, installed
+        api_base(), candidate, platform, current, installed
     );
-    let response = reqwest::blocking::get(&url)?;
-    Ok(response.text()?)
+    fetch(&url)
 }
 
 /// Parses version information from comma-separated text.
@@ -94,6 +116,10 @@ pub fn parse_versions_text(text: &str) -> Vec<VersionInfo> {
 
 /// Fetches all available versions for a candidate.
 ///
+/// Successful responses are written to the metadata cache
+/// (`~/.sdkman/var/metadata`). If the request fails, the cached list is
+/// returned instead so version lookups keep working offline.
+///
 /// # Arguments
 /// * `candidate` - Candidate name (e.g., "java")
 /// * `platform` - Platform identifier (e.g., "linuxx64")
@@ -102,12 +128,43 @@ pub fn parse_versions_text(text: &str) -> Vec<VersionInfo> {
 /// Vector of VersionInfo structs
 ///
 /// # Errors
-/// Returns error if network request fails or response cannot be parsed
+/// Returns error if network request fails and no cached list exists
 pub fn get_versions(candidate: &str, platform: &str) -> Result<Vec<VersionInfo>, Box<dyn Error>> {
-    let url = format!("{}/candidates/{}/{}/versions/all", API_BASE, candidate, platform);
-    let response = reqwest::blocking::get(&url)?;
-    let text = response.text()?;
-    Ok(parse_versions_text(&text))
+    let url = format!("{}/candidates/{}/{}/versions/all", api_base(), candidate, platform);
+    match fetch(&url) {
+        Ok(text) => {
+            // Caching is best-effort; a read-only SDKMAN_DIR must not break lookups
+            if let Ok(path) = versions_cache_path(candidate, platform) {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).ok();
+                }
+                std::fs::write(&path, &text).ok();
+            }
+            Ok(parse_versions_text(&text))
+        }
+        Err(e) => get_cached_versions(candidate, platform).ok_or(e),
+    }
+}
+
+/// Reads the version list for a candidate from the metadata cache.
+///
+/// # Arguments
+/// * `candidate` - Candidate name (e.g., "java")
+/// * `platform` - Platform identifier (e.g., "linuxx64")
+///
+/// # Returns
+/// Some(versions) if the candidate was fetched before, None otherwise
+pub fn get_cached_versions(candidate: &str, platform: &str) -> Option<Vec<VersionInfo>> {
+    let path = versions_cache_path(candidate, platform).ok()?;
+    let text = std::fs::read_to_string(path).ok()?;
+    Some(parse_versions_text(&text))
+}
+
+fn versions_cache_path(candidate: &str, platform: &str) -> Result<PathBuf, Box<dyn Error>> {
+    Ok(env::sdkman_dir()?
+        .join(constants::VAR_DIR)
+        .join(constants::METADATA_DIR)
+        .join(format!("{}-{}.versions", candidate, platform)))
 }
 
 /// Gets the default (latest) version for a candidate.
@@ -136,7 +193,7 @@ pub fn get_default_version(candidate: &str, platform: &str) -> Result<String, Bo
 /// * `version` - Version identifier (e.g., "17.0.9-oracle")
 /// * `platform` - Platform identifier (e.g., "linuxx64")
 pub fn get_download_url(candidate: &str, version: &str, platform: &str) -> String {
-    format!("{}/broker/download/{}/{}/{}", API_BASE, candidate, version, platform)
+    format!("{}/broker/download/{}/{}/{}", api_base(), candidate, version, platform)
 }
 
 /// Validates that a candidate exists in the SDKMAN registry.
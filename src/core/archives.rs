@@ -0,0 +1,270 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use sha2::{Digest, Sha256};
+use crate::constants;
+use crate::core::config::Config;
+
+/// Extension of the checksum file stored next to each cached archive.
+const CHECKSUM_EXT: &str = "sha256";
+
+/// Serializes cache writes of concurrent installs (`sdk env install` with
+/// `parallelism`), so one store's eviction never races another store.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A downloaded archive kept for reuse by later installs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedArchive {
+    pub candidate: String,
+    pub version: String,
+    pub platform: String,
+    pub path: PathBuf,
+    pub size: u64,
+    /// SHA-256 of the archive recorded when it was cached.
+    pub sha256: String,
+    /// Last time the archive was stored or reused.
+    pub used_at: Option<SystemTime>,
+}
+
+/// Cache of downloaded archives shared by every `SDKMAN_DIR`.
+///
+/// Archives are stored as `<dir>/<candidate>/<version>/<platform>.<ext>` with
+/// their SHA-256 in a sibling `.sha256` file. An archive whose contents no
+/// longer match its checksum is dropped instead of reused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveCache {
+    pub dir: PathBuf,
+    /// Size limit in bytes; 0 disables the cache.
+    pub max_size: u64,
+}
+
+/// Returns the archive cache directory: `archive_dir` from the plugin
+/// settings, otherwise `~/.sdkman/archives`.
+///
+/// The default deliberately ignores `SDKMAN_DIR` so installations into
+/// separate SDKMAN directories reuse the same downloads.
+pub fn archives_dir() -> Option<PathBuf> {
+    Config::load().archive_dir.or_else(|| {
+        dirs::home_dir().map(|h| h.join(constants::SDKMAN_DIR_NAME).join(constants::ARCHIVES_DIR))
+    })
+}
+
+impl ArchiveCache {
+    /// Opens the cache configured in the plugin settings.
+    ///
+    /// # Errors
+    /// Returns error if the home directory cannot be determined
+    pub fn open() -> Result<Self, Box<dyn Error>> {
+        let dir = archives_dir().ok_or("Could not determine archive cache directory")?;
+        Ok(ArchiveCache { dir, max_size: Config::load().archive_cache_size })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_size > 0
+    }
+
+    /// Returns the cached archive for a candidate version, if present and intact.
+    ///
+    /// Reused archives are marked as recently used so eviction keeps them.
+    pub fn lookup(&self, candidate: &str, version: &str, platform: &str) -> Option<PathBuf> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let path = self.find(candidate, version, platform)?;
+        let expected = std::fs::read_to_string(path.with_extension(checksum_ext(&path))).ok()?;
+
+        if sha256_file(&path).ok()? != expected.trim() {
+            remove_archive(&path);
+            return None;
+        }
+
+        if let Ok(file) = File::options().append(true).open(&path) {
+            file.set_modified(SystemTime::now()).ok();
+        }
+        Some(path)
+    }
+
+    /// Moves a downloaded archive into the cache and evicts the least recently
+    /// used archives beyond the size limit.
+    ///
+    /// # Arguments
+    /// * `archive` - Downloaded archive; its extension is kept
+    ///
+    /// # Returns
+    /// Path of the cached archive (or `archive` itself if caching is disabled)
+    ///
+    /// # Errors
+    /// Returns error if the archive cannot be read or moved
+    pub fn store(&self, candidate: &str, version: &str, platform: &str, archive: &Path) -> Result<PathBuf, Box<dyn Error>> {
+        if !self.is_enabled() {
+            return Ok(archive.to_path_buf());
+        }
+
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let ext = archive_ext(archive);
+        let dir = self.dir.join(candidate).join(version);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.{}", platform, ext));
+
+        let checksum = sha256_file(archive)?;
+        if std::fs::rename(archive, &path).is_err() {
+            std::fs::copy(archive, &path)?;
+            std::fs::remove_file(archive).ok();
+        }
+        std::fs::write(path.with_extension(checksum_ext(&path)), &checksum)?;
+
+        self.evict_unlocked(Some(&path));
+        Ok(path)
+    }
+
+    /// Lists every cached archive, most recently used first.
+    pub fn entries(&self) -> Vec<CachedArchive> {
+        let mut entries = Vec::new();
+        for candidate_dir in read_dirs(&self.dir) {
+            for version_dir in read_dirs(&candidate_dir) {
+                let files = std::fs::read_dir(&version_dir)
+                    .map(|e| e.filter_map(|e| e.ok()).map(|e| e.path()).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                for path in files.into_iter().filter(|p| p.is_file() && !is_checksum(p)) {
+                    if let Some(entry) = cached_archive(&candidate_dir, &version_dir, path) {
+                        entries.push(entry);
+                    }
+                }
+            }
+        }
+        entries.sort_by_key(|e| std::cmp::Reverse(e.used_at));
+        entries
+    }
+
+    /// Total size of the cached archives in bytes.
+    pub fn size(&self) -> u64 {
+        self.entries().iter().map(|e| e.size).sum()
+    }
+
+    /// Removes the least recently used archives until the cache fits its size limit.
+    ///
+    /// # Arguments
+    /// * `keep` - Archive that must survive (e.g. the one just stored)
+    ///
+    /// # Returns
+    /// The removed archives
+    pub fn evict(&self, keep: Option<&Path>) -> Vec<CachedArchive> {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        self.evict_unlocked(keep)
+    }
+
+    fn evict_unlocked(&self, keep: Option<&Path>) -> Vec<CachedArchive> {
+        let mut entries = self.entries();
+        let mut total: u64 = entries.iter().map(|e| e.size).sum();
+        let mut removed = Vec::new();
+
+        while total > self.max_size {
+            let Some(index) = entries.iter().rposition(|e| Some(e.path.as_path()) != keep) else {
+                break;
+            };
+            let entry = entries.remove(index);
+            remove_archive(&entry.path);
+            total -= entry.size;
+            removed.push(entry);
+        }
+
+        removed
+    }
+
+    /// Removes every cached archive.
+    ///
+    /// # Returns
+    /// Number of archives removed
+    ///
+    /// # Errors
+    /// Returns error if the cache directory cannot be removed
+    pub fn clear(&self) -> Result<usize, Box<dyn Error>> {
+        let count = self.entries().len();
+        if self.dir.exists() {
+            std::fs::remove_dir_all(&self.dir)?;
+        }
+        Ok(count)
+    }
+
+    fn find(&self, candidate: &str, version: &str, platform: &str) -> Option<PathBuf> {
+        std::fs::read_dir(self.dir.join(candidate).join(version))
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| !is_checksum(p) && p.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                n.strip_prefix(platform).is_some_and(|rest| rest.starts_with('.'))
+            }))
+    }
+}
+
+/// Computes the hex-encoded SHA-256 of a file.
+///
+/// # Errors
+/// Returns error if the file cannot be read
+pub fn sha256_file(path: &Path) -> Result<String, Box<dyn Error>> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn cached_archive(candidate_dir: &Path, version_dir: &Path, path: PathBuf) -> Option<CachedArchive> {
+    let name = path.file_name()?.to_str()?;
+    let platform = name.split('.').next()?.to_string();
+    let metadata = path.metadata().ok()?;
+    let sha256 = std::fs::read_to_string(path.with_extension(checksum_ext(&path)))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+
+    Some(CachedArchive {
+        candidate: candidate_dir.file_name()?.to_string_lossy().into_owned(),
+        version: version_dir.file_name()?.to_string_lossy().into_owned(),
+        platform,
+        size: metadata.len(),
+        sha256,
+        used_at: metadata.modified().ok(),
+        path,
+    })
+}
+
+/// Archive extension including compound ones (`tar.gz`).
+fn archive_ext(path: &Path) -> String {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if name.ends_with(".tar.gz") {
+        "tar.gz".to_string()
+    } else {
+        path.extension().and_then(|e| e.to_str()).unwrap_or("archive").to_string()
+    }
+}
+
+/// Extension of the checksum file for an archive, replacing only the last
+/// extension component so `linuxx64.tar.gz` maps to `linuxx64.tar.gz.sha256`.
+fn checksum_ext(path: &Path) -> String {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    format!("{}.{}", ext, CHECKSUM_EXT)
+}
+
+fn is_checksum(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == CHECKSUM_EXT)
+}
+
+fn remove_archive(path: &Path) {
+    std::fs::remove_file(path).ok();
+    std::fs::remove_file(path.with_extension(checksum_ext(path))).ok();
+}
+
+fn read_dirs(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,198 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tempfile::TempDir;
+use crate::constants;
+use crate::core::{env, init, install, shims};
+use crate::utils::{archive, fs};
+
+/// Name of the manifest stored at the root of a bundle.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Manifest format written by this version of the plugin.
+pub const BUNDLE_FORMAT: u32 = 1;
+
+/// A candidate version packed in a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub candidate: String,
+    pub version: String,
+}
+
+/// Describes the contents of a bundle.
+///
+/// Versions are stored under `candidates/<candidate>/<version>/` next to
+/// the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    /// Platform the packed installations were built for.
+    pub platform: String,
+    /// Creation time in RFC 3339 format.
+    pub created: String,
+    pub entries: Vec<BundleEntry>,
+}
+
+/// Result of installing one bundle entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledEntry {
+    pub entry: BundleEntry,
+    /// `false` if the version was already installed and left untouched.
+    pub installed: bool,
+}
+
+/// Parses a `candidate@version` spec; a bare candidate selects its current version.
+///
+/// # Errors
+/// Returns error if no version is given and the candidate has no current version
+pub fn parse_spec(spec: &str) -> Result<BundleEntry, Box<dyn Error>> {
+    let (candidate, version) = match spec.split_once('@') {
+        Some((candidate, version)) => (candidate.to_string(), version.to_string()),
+        None => {
+            let version = env::get_current_version(spec)
+                .ok_or_else(|| format!("{} has no current version; use {}@<version>", spec, spec))?;
+            (spec.to_string(), version)
+        }
+    };
+    Ok(BundleEntry { candidate, version })
+}
+
+/// Reads the versions pinned in a `.sdkmanrc` file, sorted by candidate.
+///
+/// # Errors
+/// Returns error if the file cannot be read
+pub fn entries_from_sdkmanrc(path: &Path) -> Result<Vec<BundleEntry>, Box<dyn Error>> {
+    let mut entries: Vec<BundleEntry> = env::read_sdkmanrc(path)?
+        .into_iter()
+        .map(|(candidate, version)| BundleEntry { candidate, version })
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// Packs installed versions and a manifest into a tar.gz bundle.
+///
+/// Linked versions are packed with the contents of their target so the
+/// bundle is self-contained.
+///
+/// # Errors
+/// Returns error if a version is not installed or the archive cannot be written
+pub fn create(bundle: &Path, entries: &[BundleEntry], platform: &str) -> Result<Manifest, Box<dyn Error>> {
+    if entries.is_empty() {
+        return Err("Nothing to bundle".into());
+    }
+
+    let mut sources = Vec::new();
+    for entry in entries {
+        validate_entry(entry)?;
+        let dir = env::candidate_dir(&entry.candidate, &entry.version)?;
+        if !dir.is_dir() {
+            return Err(format!("{} {} is not installed", entry.candidate, entry.version).into());
+        }
+        sources.push((dir.canonicalize()?, entry_path(entry)));
+    }
+
+    let manifest = Manifest {
+        format: BUNDLE_FORMAT,
+        platform: platform.to_string(),
+        created: chrono::Utc::now().to_rfc3339(),
+        entries: entries.to_vec(),
+    };
+
+    let staging = TempDir::with_prefix("sdkman-bundle-create-")?;
+    let manifest_path = staging.path().join(MANIFEST_FILE);
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    let mut archive_entries: Vec<(&Path, &Path)> = vec![(&manifest_path, Path::new(MANIFEST_FILE))];
+    archive_entries.extend(sources.iter().map(|(src, name)| (src.as_path(), name.as_path())));
+    archive::create_tar_gz(bundle, &archive_entries)?;
+    Ok(manifest)
+}
+
+/// Reads the manifest of a bundle without installing it.
+///
+/// # Errors
+/// Returns error if the bundle cannot be read or has no valid manifest
+pub fn read_manifest(bundle: &Path) -> Result<Manifest, Box<dyn Error>> {
+    let content = archive::read_tar_gz_file(bundle, Path::new(MANIFEST_FILE))?
+        .ok_or_else(|| format!("Not a bundle: {} is missing", MANIFEST_FILE))?;
+    let manifest: Manifest = serde_json::from_slice(&content)?;
+    if manifest.format > BUNDLE_FORMAT {
+        return Err(format!("Unsupported bundle format {}", manifest.format).into());
+    }
+    for entry in &manifest.entries {
+        validate_entry(entry)?;
+    }
+    Ok(manifest)
+}
+
+/// Installs every version in a bundle that isn't installed yet.
+///
+/// # Arguments
+/// * `bundle` - Path to the .tar.gz bundle
+/// * `platform` - Platform of this machine; must match the bundle unless `force`
+/// * `force` - Install even if the bundle was built for another platform
+///
+/// # Errors
+/// Returns error if the bundle is invalid, built for another platform, or
+/// a version cannot be installed
+pub fn install(bundle: &Path, platform: &str, force: bool) -> Result<(Manifest, Vec<InstalledEntry>), Box<dyn Error>> {
+    let staging = TempDir::with_prefix("sdkman-bundle-install-")?;
+    let result = install_from(staging.path(), bundle, platform, force);
+    init::refresh_init_script();
+    shims::refresh();
+    result
+}
+
+fn install_from(staging: &Path, bundle: &Path, platform: &str, force: bool) -> Result<(Manifest, Vec<InstalledEntry>), Box<dyn Error>> {
+    // Validated before anything is extracted: entries end up in paths
+    let manifest = read_manifest(bundle)?;
+
+    if manifest.platform != platform && !force {
+        return Err(format!(
+            "Bundle was created for {} but this platform is {} (use --force to install anyway)",
+            manifest.platform, platform
+        ).into());
+    }
+
+    let extract_dir = staging.join("source");
+    archive::extract_tar_gz(bundle, &extract_dir)?;
+
+    let mut results = Vec::new();
+    for (i, entry) in manifest.entries.iter().enumerate() {
+        if env::is_installed(&entry.candidate, &entry.version) {
+            results.push(InstalledEntry { entry: entry.clone(), installed: false });
+            continue;
+        }
+
+        let packed = extract_dir.join(entry_path(entry));
+        if !packed.is_dir() {
+            return Err(format!("Bundle is missing {} {}", entry.candidate, entry.version).into());
+        }
+
+        // Wrap the version in its own directory so move_and_normalize
+        // unwraps exactly one level, as for a downloaded archive
+        let wrapper = staging.join(format!("entry-{}", i));
+        std::fs::create_dir_all(&wrapper)?;
+        std::fs::rename(&packed, wrapper.join(&entry.version))?;
+
+        let install_dir = env::candidate_dir(&entry.candidate, &entry.version)?;
+        install::move_and_normalize(&wrapper, &install_dir)?;
+        results.push(InstalledEntry { entry: entry.clone(), installed: true });
+    }
+
+    Ok((manifest, results))
+}
+
+/// Rejects entries whose candidate or version isn't a single plain path component.
+fn validate_entry(entry: &BundleEntry) -> Result<(), Box<dyn Error>> {
+    if !fs::is_plain_name(&entry.candidate) || !fs::is_plain_name(&entry.version) {
+        return Err(format!("Invalid bundle entry {}@{}", entry.candidate, entry.version).into());
+    }
+    Ok(())
+}
+
+/// Path of an entry inside the bundle.
+fn entry_path(entry: &BundleEntry) -> PathBuf {
+    Path::new(constants::CANDIDATES_DIR).join(&entry.candidate).join(&entry.version)
+}
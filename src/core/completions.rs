@@ -0,0 +1,46 @@
+use std::path::Path;
+use crate::core::{api, config, env};
+use crate::core::version::VersionId;
+
+/// Candidate names to complete: the cached candidate list from
+/// `~/.sdkman/var/candidates` plus anything installed.
+///
+/// Never touches the network, so completion stays instant; the cache is
+/// refreshed by `sdk update` and `sdk install`.
+pub fn candidates() -> Vec<String> {
+    let mut candidates = config::cached_candidates().unwrap_or_default();
+    candidates.extend(env::get_installed_candidates());
+    candidates.retain(|c| !c.is_empty());
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+/// Installed versions of a candidate, newest first.
+pub fn installed_versions(candidate: &str) -> Vec<String> {
+    newest_first(env::get_installed_versions(candidate))
+}
+
+/// Versions of a candidate usable in `project_dir`, including isolated
+/// installations in its `.sdkman`, newest first.
+pub fn installed_versions_in(candidate: &str, project_dir: &Path) -> Vec<String> {
+    newest_first(env::installed_versions_in(candidate, project_dir))
+}
+
+/// Versions of a candidate that can be installed on `platform` (the
+/// detected one if `None`), newest first.
+///
+/// Only reads the metadata cache, so TAB never waits on the network;
+/// nothing is offered for candidates never listed for this platform.
+pub fn available_versions(candidate: &str, platform: Option<&str>) -> Vec<String> {
+    let Ok(platform) = env::resolve_platform(platform) else { return Vec::new() };
+    let versions = api::get_cached_versions(candidate, &platform).unwrap_or_default();
+    newest_first(versions.into_iter().map(|v| v.version).collect())
+}
+
+fn newest_first(versions: Vec<String>) -> Vec<String> {
+    let mut parsed: Vec<VersionId> = versions.iter().map(|v| VersionId::parse(v)).collect();
+    parsed.sort_by(|a, b| b.cmp_version(a));
+    parsed.dedup_by(|a, b| a.raw == b.raw);
+    parsed.into_iter().map(|v| v.raw).collect()
+}
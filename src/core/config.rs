@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use crate::constants;
+use crate::core::env;
+
+// Keys of `~/.sdkman/etc/config` shared with bash SDKMAN
+pub const AUTO_ANSWER: &str = "sdkman_auto_answer";
+pub const AUTO_ENV: &str = "sdkman_auto_env";
+pub const INSECURE_SSL: &str = "sdkman_insecure_ssl";
+pub const CURL_CONNECT_TIMEOUT: &str = "sdkman_curl_connect_timeout";
+pub const CURL_MAX_TIME: &str = "sdkman_curl_max_time";
+pub const ROSETTA2_COMPATIBLE: &str = "sdkman_rosetta2_compatible";
+
+pub const DEFAULT_API_BASE: &str = "https://api.sdkman.io/2";
+
+/// Default size limit of the downloaded archive cache (2 GiB).
+pub const DEFAULT_ARCHIVE_CACHE_SIZE: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Type of a config value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Bool,
+    Int,
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueType::Bool => write!(f, "bool"),
+            ValueType::Int => write!(f, "int"),
+        }
+    }
+}
+
+/// Keys understood by bash SDKMAN, with their types and default values.
+pub const KNOWN_KEYS: &[(&str, ValueType, &str)] = &[
+    (AUTO_ANSWER, ValueType::Bool, "false"),
+    ("sdkman_auto_complete", ValueType::Bool, "true"),
+    (AUTO_ENV, ValueType::Bool, "false"),
+    ("sdkman_auto_update", ValueType::Bool, "true"),
+    ("sdkman_beta_channel", ValueType::Bool, "false"),
+    ("sdkman_checksum_enable", ValueType::Bool, "true"),
+    ("sdkman_colour_enable", ValueType::Bool, "true"),
+    (CURL_CONNECT_TIMEOUT, ValueType::Int, "7"),
+    (CURL_MAX_TIME, ValueType::Int, "10"),
+    ("sdkman_debug_mode", ValueType::Bool, "false"),
+    (INSECURE_SSL, ValueType::Bool, "false"),
+    (ROSETTA2_COMPATIBLE, ValueType::Bool, "false"),
+    ("sdkman_selfupdate_feature", ValueType::Bool, "true"),
+];
+
+/// Returns the type and default value of a known key.
+pub fn known_key(key: &str) -> Option<(ValueType, &'static str)> {
+    KNOWN_KEYS
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, ty, default)| (*ty, *default))
+}
+
+/// Checks that a key is known and the value has the right type.
+///
+/// # Errors
+/// Returns a message describing the unknown key or invalid value
+pub fn validate(key: &str, value: &str) -> Result<(), String> {
+    let (ty, _) = known_key(key).ok_or_else(|| {
+        let keys: Vec<&str> = KNOWN_KEYS.iter().map(|(k, _, _)| *k).collect();
+        format!("Unknown config key: {}. Known keys: {}", key, keys.join(", "))
+    })?;
+    let valid = match ty {
+        ValueType::Bool => value == "true" || value == "false",
+        ValueType::Int => value.parse::<u64>().is_ok(),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid value for {}: expected {}, got '{}'", key, ty, value))
+    }
+}
+
+/// Where `sdk env install` puts the versions of a local environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnvMode {
+    /// Install into `~/.sdkman` and link from the project's `.sdkman`.
+    #[default]
+    Shared,
+    /// Install into the project's `.sdkman/candidates`.
+    Isolated,
+}
+
+impl std::str::FromStr for EnvMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shared" => Ok(EnvMode::Shared),
+            "isolated" => Ok(EnvMode::Isolated),
+            other => Err(format!("Unknown env mode: {} (expected shared or isolated)", other)),
+        }
+    }
+}
+
+impl fmt::Display for EnvMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvMode::Shared => write!(f, "shared"),
+            EnvMode::Isolated => write!(f, "isolated"),
+        }
+    }
+}
+
+/// A line of the config file, kept verbatim unless it is an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Line {
+    Entry { key: String, value: String },
+    Other(String),
+}
+
+/// The `key=value` config file used by bash SDKMAN (`~/.sdkman/etc/config`).
+///
+/// Comments, blank lines and entry order are preserved so the file can be
+/// rewritten without disturbing settings managed by the bash CLI.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigFile {
+    lines: Vec<Line>,
+}
+
+impl ConfigFile {
+    /// Parses config file content.
+    pub fn parse(content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim();
+                match trimmed.split_once('=') {
+                    Some((key, value)) if !trimmed.starts_with('#') && !key.trim().is_empty() => Line::Entry {
+                        key: key.trim().to_string(),
+                        value: value.trim().to_string(),
+                    },
+                    _ => Line::Other(line.to_string()),
+                }
+            })
+            .collect();
+        ConfigFile { lines }
+    }
+
+    /// Loads `~/.sdkman/etc/config`, returning an empty config if it does not exist.
+    ///
+    /// # Errors
+    /// Returns error if the SDKMAN directory cannot be determined or the file
+    /// exists but cannot be read
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let path = config_path()?;
+        if !path.exists() {
+            return Ok(ConfigFile::default());
+        }
+        Ok(ConfigFile::parse(&std::fs::read_to_string(&path)?))
+    }
+
+    /// Writes the config back to `~/.sdkman/etc/config`.
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be written
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = config_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Returns the value of a key (the last one wins, as when bash sources the file).
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries().filter(|(k, _)| *k == key).map(|(_, v)| v).last()
+    }
+
+    /// Sets a key, updating it in place or appending it if absent.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let mut found = false;
+        for line in &mut self.lines {
+            if let Line::Entry { key: k, value: v } = line {
+                if k == key {
+                    *v = value.to_string();
+                    found = true;
+                }
+            }
+        }
+        if !found {
+            self.lines.push(Line::Entry { key: key.to_string(), value: value.to_string() });
+        }
+    }
+
+    /// Removes a key. Returns true if it was present.
+    pub fn unset(&mut self, key: &str) -> bool {
+        let before = self.lines.len();
+        self.lines.retain(|line| !matches!(line, Line::Entry { key: k, .. } if k == key));
+        self.lines.len() != before
+    }
+
+    /// Iterates over all `key=value` entries in file order.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Entry { key, value } => Some((key.as_str(), value.as_str())),
+            Line::Other(_) => None,
+        })
+    }
+
+    fn bool(&self, key: &str, default: bool) -> bool {
+        self.get(key).and_then(|v| v.parse().ok()).unwrap_or(default)
+    }
+
+    fn u64(&self, key: &str, default: u64) -> u64 {
+        self.get(key).and_then(|v| v.parse().ok()).unwrap_or(default)
+    }
+}
+
+impl fmt::Display for ConfigFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            match line {
+                Line::Entry { key, value } => writeln!(f, "{}={}", key, value)?,
+                Line::Other(text) => writeln!(f, "{}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Settings from Nushell's `$env.config.plugins.sdkman`.
+///
+/// Every field is optional; set fields take precedence over `etc/config`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginSettings {
+    pub api_base_url: Option<String>,
+    pub offline: Option<bool>,
+    pub platform: Option<String>,
+    pub parallelism: Option<usize>,
+    pub auto_env: Option<bool>,
+    /// Candidate name to HOME variable name (e.g. `java` -> `JAVA_HOME`).
+    pub home_vars: HashMap<String, String>,
+    pub archive_dir: Option<PathBuf>,
+    pub archive_cache_size: Option<u64>,
+    pub env_mode: Option<EnvMode>,
+    pub relative_links: Option<bool>,
+    /// Keys that were not recognized and are ignored (typos, or options of
+    /// a newer release).
+    pub ignored_keys: Vec<String>,
+}
+
+// The plugin process is long-lived and serves one call at a time, so the
+// settings of the latest call are kept here for the core functions to read.
+static PLUGIN_SETTINGS: RwLock<Option<PluginSettings>> = RwLock::new(None);
+
+/// Replaces the plugin settings used by [`Config::load`].
+pub fn set_plugin_settings(settings: PluginSettings) {
+    if let Ok(mut current) = PLUGIN_SETTINGS.write() {
+        *current = Some(settings);
+    }
+}
+
+/// Returns the plugin settings from the latest call (empty if none were loaded).
+pub fn plugin_settings() -> PluginSettings {
+    PLUGIN_SETTINGS
+        .read()
+        .ok()
+        .and_then(|s| s.clone())
+        .unwrap_or_default()
+}
+
+/// Typed view of the settings the plugin honors, with bash SDKMAN defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Switch versions automatically when entering a directory with `.sdkmanrc`.
+    pub auto_env: bool,
+    /// Skip TLS certificate verification.
+    pub insecure_ssl: bool,
+    /// Connection timeout for HTTP requests, in seconds.
+    pub curl_connect_timeout: u64,
+    /// Total timeout for API requests (not downloads), in seconds.
+    pub curl_max_time: u64,
+    /// Use x86_64 binaries on Apple Silicon.
+    pub rosetta2_compatible: bool,
+    /// Base URL of the SDKMAN candidates API.
+    pub api_base_url: String,
+    /// Skip network requests and rely on local caches.
+    pub offline: bool,
+    /// Platform identifier overriding detection.
+    pub platform: Option<String>,
+    /// Number of concurrent installs.
+    pub parallelism: usize,
+    /// Candidate name to HOME variable name overrides.
+    pub home_vars: HashMap<String, String>,
+    /// Directory of the downloaded archive cache (defaults to `~/.sdkman/archives`).
+    pub archive_dir: Option<PathBuf>,
+    /// Size limit of the archive cache in bytes; 0 disables caching.
+    pub archive_cache_size: u64,
+    /// Whether local environments share `~/.sdkman` installs or keep their own.
+    pub env_mode: EnvMode,
+    /// Create `current` symlinks relative to their location, so the SDKMAN
+    /// directory or a project's `.sdkman` can be moved.
+    pub relative_links: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::from(&ConfigFile::default())
+    }
+}
+
+impl From<&ConfigFile> for Config {
+    fn from(file: &ConfigFile) -> Self {
+        Config {
+            auto_env: file.bool(AUTO_ENV, false),
+            insecure_ssl: file.bool(INSECURE_SSL, false),
+            curl_connect_timeout: file.u64(CURL_CONNECT_TIMEOUT, 7),
+            curl_max_time: file.u64(CURL_MAX_TIME, 10),
+            rosetta2_compatible: file.bool(ROSETTA2_COMPATIBLE, false),
+            api_base_url: DEFAULT_API_BASE.to_string(),
+            offline: false,
+            platform: None,
+            parallelism: 1,
+            home_vars: HashMap::new(),
+            archive_dir: None,
+            archive_cache_size: DEFAULT_ARCHIVE_CACHE_SIZE,
+            env_mode: EnvMode::Shared,
+            relative_links: false,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the effective settings.
+    ///
+    /// Reads `~/.sdkman/etc/config` and applies the plugin settings from
+    /// Nushell's config on top. Missing or unreadable files and malformed
+    /// values fall back to the bash SDKMAN defaults.
+    pub fn load() -> Self {
+        let mut config = ConfigFile::load()
+            .map(|file| Config::from(&file))
+            .unwrap_or_default();
+        config.apply(plugin_settings());
+        config
+    }
+
+    /// Overrides settings with the fields set in `settings`.
+    pub fn apply(&mut self, settings: PluginSettings) {
+        if let Some(url) = settings.api_base_url {
+            self.api_base_url = url.trim_end_matches('/').to_string();
+        }
+        if let Some(offline) = settings.offline {
+            self.offline = offline;
+        }
+        if settings.platform.is_some() {
+            self.platform = settings.platform;
+        }
+        if let Some(parallelism) = settings.parallelism {
+            self.parallelism = parallelism.max(1);
+        }
+        if let Some(auto_env) = settings.auto_env {
+            self.auto_env = auto_env;
+        }
+        self.home_vars.extend(settings.home_vars);
+        if settings.archive_dir.is_some() {
+            self.archive_dir = settings.archive_dir;
+        }
+        if let Some(size) = settings.archive_cache_size {
+            self.archive_cache_size = size;
+        }
+        if let Some(mode) = settings.env_mode {
+            self.env_mode = mode;
+        }
+        if let Some(relative) = settings.relative_links {
+            self.relative_links = relative;
+        }
+    }
+
+    /// Returns the HOME variable name for a candidate (e.g. `JAVA_HOME`).
+    pub fn home_var(&self, candidate: &str) -> String {
+        self.home_vars
+            .get(candidate)
+            .cloned()
+            .unwrap_or_else(|| format!("{}_HOME", candidate.to_uppercase().replace('-', "_")))
+    }
+}
+
+/// Returns the path of `~/.sdkman/etc/config`.
+pub fn config_path() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(env::sdkman_dir()?.join(constants::ETC_DIR).join(constants::CONFIG_FILE))
+}
+
+/// Reads a state file from `~/.sdkman/var` (e.g. `version`, `platform`).
+///
+/// # Returns
+/// Trimmed file content, or None if the file does not exist or is empty
+pub fn read_var(name: &str) -> Option<String> {
+    let path = env::sdkman_dir().ok()?.join(constants::VAR_DIR).join(name);
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Writes a state file to `~/.sdkman/var`.
+///
+/// # Errors
+/// Returns error if the var directory or file cannot be written
+pub fn write_var(name: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    let var_dir = env::sdkman_dir()?.join(constants::VAR_DIR);
+    std::fs::create_dir_all(&var_dir)?;
+    std::fs::write(var_dir.join(name), value)?;
+    Ok(())
+}
+
+/// Reads the candidate list cached in `~/.sdkman/var/candidates`.
+pub fn cached_candidates() -> Option<Vec<String>> {
+    read_var(constants::CANDIDATES_FILE).map(|text| {
+        text.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    })
+}
+
+/// Stores the candidate list in `~/.sdkman/var/candidates` (comma-separated).
+///
+/// # Errors
+/// Returns error if the file cannot be written
+pub fn write_cached_candidates(candidates: &[String]) -> Result<(), Box<dyn Error>> {
+    write_var(constants::CANDIDATES_FILE, &candidates.join(","))
+}
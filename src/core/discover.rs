@@ -0,0 +1,184 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::constants;
+use crate::core::{env, install};
+use crate::core::install::InstallSource;
+use crate::utils::fs;
+
+/// Prefix of version names created for system installations.
+pub const SYSTEM_PREFIX: &str = "system";
+
+/// Where to look for system installations of a candidate.
+struct SearchPath {
+    candidate: &'static str,
+    /// Directory holding one installation per entry (e.g. `/usr/lib/jvm`).
+    parent: Option<&'static str>,
+    /// A single installation directory (e.g. `/usr/share/maven`).
+    home: Option<&'static str>,
+    /// Subdirectory of each entry holding the actual home (macOS bundles).
+    suffix: &'static str,
+    /// Executable in `bin/` that prints the version with `--version`.
+    binary: &'static str,
+}
+
+const SEARCH_PATHS: &[SearchPath] = &[
+    SearchPath { candidate: "java", parent: Some("/usr/lib/jvm"), home: None, suffix: "", binary: "java" },
+    SearchPath { candidate: "java", parent: Some("/usr/java"), home: None, suffix: "", binary: "java" },
+    SearchPath { candidate: "java", parent: Some("/Library/Java/JavaVirtualMachines"), home: None, suffix: "Contents/Home", binary: "java" },
+    SearchPath { candidate: "maven", parent: None, home: Some("/usr/share/maven"), suffix: "", binary: "mvn" },
+    SearchPath { candidate: "maven", parent: None, home: Some("/opt/maven"), suffix: "", binary: "mvn" },
+    SearchPath { candidate: "gradle", parent: None, home: Some("/usr/share/gradle"), suffix: "", binary: "gradle" },
+    SearchPath { candidate: "gradle", parent: None, home: Some("/opt/gradle"), suffix: "", binary: "gradle" },
+    SearchPath { candidate: "ant", parent: None, home: Some("/usr/share/ant"), suffix: "", binary: "ant" },
+];
+
+/// A system installation found by [`discover`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discovered {
+    pub candidate: String,
+    /// Version name to register it under (e.g. `system-17`).
+    pub name: String,
+    /// Detected version (e.g. `17.0.9`).
+    pub version: String,
+    /// Canonical installation directory.
+    pub path: PathBuf,
+    /// Installed version that already links to this directory, if any.
+    pub linked_as: Option<String>,
+}
+
+/// Scans the common system locations for installations of known candidates.
+///
+/// # Arguments
+/// * `candidate` - Only look for this candidate
+///
+/// # Returns
+/// Installations with a detectable version, deduplicated by canonical path
+pub fn discover(candidate: Option<&str>) -> Vec<Discovered> {
+    let mut found = Vec::new();
+    for search in SEARCH_PATHS.iter().filter(|s| candidate.is_none_or(|c| c == s.candidate)) {
+        let homes: Vec<PathBuf> = match (search.parent, search.home) {
+            (Some(parent), _) => read_dirs(Path::new(parent))
+                .into_iter()
+                .map(|dir| dir.join(search.suffix))
+                .collect(),
+            (None, Some(home)) => vec![PathBuf::from(home)],
+            (None, None) => Vec::new(),
+        };
+        found.extend(inspect_homes(search.candidate, search.binary, &homes, &found));
+    }
+    found
+}
+
+/// Inspects candidate home directories and names them `system-<major>`.
+///
+/// Directories without `bin/<binary>` or a detectable version are ignored.
+/// Directories already present in `known` (e.g. `default-java` linking to
+/// an inspected JDK) are skipped; name clashes get a numeric suffix.
+pub fn inspect_homes(candidate: &str, binary: &str, homes: &[PathBuf], known: &[Discovered]) -> Vec<Discovered> {
+    let mut found: Vec<Discovered> = Vec::new();
+    for home in homes {
+        let Ok(path) = home.canonicalize() else { continue };
+        if !path.join("bin").join(binary).exists() || known.iter().chain(&found).any(|d| d.path == path) {
+            continue;
+        }
+        let Some(version) = detect_version(&path, binary) else { continue };
+
+        let base = format!("{}-{}", SYSTEM_PREFIX, major(candidate, &version));
+        let taken = |name: &str| {
+            known.iter().chain(&found).any(|d| d.candidate == candidate && d.name == name)
+                || env::is_installed(candidate, name)
+        };
+        let name = (1..)
+            .map(|n| if n == 1 { base.clone() } else { format!("{}-{}", base, n) })
+            .find(|name| !taken(name))
+            .unwrap_or(base);
+
+        found.push(Discovered {
+            candidate: candidate.to_string(),
+            linked_as: linked_as(candidate, &path),
+            name,
+            version,
+            path,
+        });
+    }
+    found
+}
+
+/// Detects the version of an installation from its `release` file (JDKs)
+/// or `bin/<binary> --version` output.
+pub fn detect_version(home: &Path, binary: &str) -> Option<String> {
+    if let Some(version) = std::fs::read_to_string(home.join("release")).ok().and_then(|c| parse_release_file(&c)) {
+        return Some(version);
+    }
+    let output = Command::new(home.join("bin").join(binary)).arg("--version").output().ok()?;
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    parse_version_output(&text)
+}
+
+/// Extracts `JAVA_VERSION` from a JDK `release` file.
+pub fn parse_release_file(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "JAVA_VERSION")
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Extracts the first version-looking token from `--version` output
+/// (e.g. `Apache Maven 3.9.6 (...)` -> `3.9.6`).
+pub fn parse_version_output(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .map(|token| token.trim_matches(|c: char| !c.is_ascii_alphanumeric()))
+        .find(|token| token.starts_with(|c: char| c.is_ascii_digit()) && token.contains('.'))
+        .map(str::to_string)
+}
+
+/// Registers an existing installation directory as a linked version.
+///
+/// # Errors
+/// Returns error if the version name is invalid or taken, or the path is not a directory
+pub fn link(candidate: &str, name: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+    if !fs::is_plain_name(name) || name == constants::CURRENT_LINK {
+        return Err(format!("Invalid version name: {}", name).into());
+    }
+    if env::is_installed(candidate, name) {
+        return Err(format!("{} {} is already installed", candidate, name).into());
+    }
+    if !path.is_dir() {
+        return Err(format!("Not a directory: {}", path.display()).into());
+    }
+    install::install_from(candidate, name, &InstallSource::Directory(path.canonicalize()?)).map(|_| ())
+}
+
+/// Major version used in `system-<major>` names.
+///
+/// Legacy Java versions report `1.8.0_392`; their major version is 8.
+fn major<'a>(candidate: &str, version: &'a str) -> &'a str {
+    let version = match candidate {
+        "java" => version.strip_prefix("1.").unwrap_or(version),
+        _ => version,
+    };
+    version.split(['.', '_', '-', '+']).next().unwrap_or(version)
+}
+
+/// Finds an installed version of `candidate` that already links to `path`.
+fn linked_as(candidate: &str, path: &Path) -> Option<String> {
+    env::get_installed_versions(candidate).into_iter().find(|version| {
+        env::candidate_dir(candidate, version)
+            .ok()
+            .filter(|dir| dir.is_symlink())
+            .and_then(|dir| dir.canonicalize().ok())
+            .is_some_and(|target| target == path)
+    })
+}
+
+fn read_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
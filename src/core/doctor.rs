@@ -0,0 +1,223 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use crate::constants;
+use crate::core::config::Config;
+use crate::core::env;
+use crate::core::install::{INSTALL_TEMP_PREFIX, LOCAL_TEMP_PREFIX};
+
+/// Install staging directories untouched for this long are considered
+/// abandoned; younger ones may belong to an install still running elsewhere.
+const LEFTOVER_MIN_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How serious a doctor finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem found by `sdk doctor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub path: PathBuf,
+    pub issue: String,
+    /// Whether `--fix` knows how to repair this finding.
+    pub fixable: bool,
+    /// Whether the finding was repaired during this run.
+    pub fixed: bool,
+}
+
+/// Collects findings while optionally applying safe repairs.
+struct Checkup {
+    fix: bool,
+    findings: Vec<Finding>,
+}
+
+impl Checkup {
+    fn report(&mut self, severity: Severity, path: &Path, issue: impl Into<String>) {
+        self.findings.push(Finding {
+            severity,
+            path: path.to_path_buf(),
+            issue: issue.into(),
+            fixable: false,
+            fixed: false,
+        });
+    }
+
+    /// Records a repairable finding and runs `repair` when fixing is enabled.
+    fn repairable(&mut self, severity: Severity, path: &Path, issue: impl Into<String>, repair: impl FnOnce() -> std::io::Result<()>) {
+        let fixed = self.fix && repair().is_ok();
+        self.findings.push(Finding {
+            severity,
+            path: path.to_path_buf(),
+            issue: issue.into(),
+            fixable: true,
+            fixed,
+        });
+    }
+}
+
+/// Inspects the SDKMAN directory, every installed candidate and the local
+/// `.sdkman` environment (if any) for inconsistencies.
+///
+/// # Arguments
+/// * `fix` - Repair findings that can be fixed without losing data
+///
+/// # Returns
+/// All findings, most severe first
+///
+/// # Errors
+/// Returns error if the SDKMAN directory cannot be determined
+pub fn diagnose(fix: bool) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let mut checkup = Checkup { fix, findings: Vec::new() };
+    let sdkman_dir = env::sdkman_dir()?;
+
+    check_sdkman_dir(&mut checkup, &sdkman_dir);
+    if sdkman_dir.is_dir() {
+        let candidates_dir = sdkman_dir.join(constants::CANDIDATES_DIR);
+        check_candidates_dir(&mut checkup, &candidates_dir);
+    }
+    check_install_leftovers(&mut checkup, &sdkman_dir.join(constants::TMP_DIR));
+    if let Some(local_dir) = env::local_sdkman_dir() {
+        check_local_env(&mut checkup, &local_dir);
+    }
+
+    checkup.findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    Ok(checkup.findings)
+}
+
+fn check_sdkman_dir(checkup: &mut Checkup, sdkman_dir: &Path) {
+    if let Ok(dir) = std::env::var(constants::ENV_SDKMAN_DIR) {
+        if !Path::new(&dir).is_absolute() {
+            checkup.report(Severity::Warning, sdkman_dir, format!("{} is a relative path and depends on the working directory", constants::ENV_SDKMAN_DIR));
+        }
+        let default_dir = dirs::home_dir().map(|h| h.join(constants::SDKMAN_DIR_NAME));
+        if let Some(default_dir) = default_dir.filter(|d| d.exists() && d != sdkman_dir) {
+            checkup.report(Severity::Info, sdkman_dir, format!("{} overrides the default installation at {}", constants::ENV_SDKMAN_DIR, default_dir.display()));
+        }
+    }
+
+    if !sdkman_dir.exists() {
+        checkup.report(Severity::Error, sdkman_dir, "SDKMAN directory does not exist");
+    } else if !sdkman_dir.is_dir() {
+        checkup.report(Severity::Error, sdkman_dir, "SDKMAN directory is not a directory");
+    }
+}
+
+fn check_candidates_dir(checkup: &mut Checkup, candidates_dir: &Path) {
+    if !candidates_dir.exists() {
+        checkup.repairable(Severity::Warning, candidates_dir, "candidates directory is missing", || {
+            std::fs::create_dir_all(candidates_dir)
+        });
+        return;
+    }
+
+    for entry in read_entries(candidates_dir) {
+        if entry.is_dir() {
+            check_candidate(checkup, &entry);
+        }
+    }
+}
+
+fn check_candidate(checkup: &mut Checkup, candidate_dir: &Path) {
+    for entry in read_entries(candidate_dir) {
+        let is_current = entry.file_name().is_some_and(|n| n == constants::CURRENT_LINK);
+        if is_current {
+            check_current_link(checkup, &entry);
+        } else {
+            check_version_dir(checkup, &entry);
+        }
+    }
+}
+
+/// Checks a `current` link (global or local).
+fn check_current_link(checkup: &mut Checkup, current: &Path) {
+    if current.is_symlink() && !current.exists() {
+        checkup.repairable(Severity::Error, current, "current link points to a missing version", || {
+            std::fs::remove_file(current)
+        });
+        return;
+    }
+
+    // On Windows `current` is intentionally a directory with a version marker
+    #[cfg(unix)]
+    if current.is_dir() && !current.is_symlink() {
+        checkup.report(Severity::Error, current, "current is a real directory instead of a link");
+    }
+
+    // Both link forms resolve; absolute ones only break when the tree moves
+    let absolute = std::fs::read_link(current).is_ok_and(|target| target.is_absolute());
+    if absolute && Config::load().relative_links {
+        checkup.repairable(Severity::Info, current, "current link is absolute but relative_links is enabled", || {
+            let target = std::fs::read_link(current)?;
+            env::link_current(current, &target).map_err(|e| std::io::Error::other(e.to_string()))
+        });
+    }
+}
+
+fn check_version_dir(checkup: &mut Checkup, version_dir: &Path) {
+    if version_dir.is_symlink() && !version_dir.exists() {
+        checkup.repairable(Severity::Error, version_dir, "linked version points to a missing directory", || {
+            std::fs::remove_file(version_dir)
+        });
+        return;
+    }
+    if !version_dir.is_dir() {
+        return;
+    }
+
+    if read_entries(version_dir).is_empty() {
+        checkup.repairable(Severity::Error, version_dir, "installation is empty (interrupted install?)", || {
+            std::fs::remove_dir(version_dir)
+        });
+    } else if !version_dir.join("bin").is_dir() {
+        checkup.report(Severity::Warning, version_dir, "installation has no bin directory");
+    }
+}
+
+fn check_install_leftovers(checkup: &mut Checkup, temp_dir: &Path) {
+    for entry in read_entries(temp_dir) {
+        let is_staging = entry
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(INSTALL_TEMP_PREFIX) || n.starts_with(LOCAL_TEMP_PREFIX));
+        let abandoned = std::fs::metadata(&entry)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age >= LEFTOVER_MIN_AGE);
+        if is_staging && abandoned && entry.is_dir() {
+            checkup.repairable(Severity::Warning, &entry, "leftover directory from an interrupted install", || {
+                std::fs::remove_dir_all(&entry)
+            });
+        }
+    }
+}
+
+fn check_local_env(checkup: &mut Checkup, local_dir: &Path) {
+    for candidate_dir in read_entries(&local_dir.join(constants::CANDIDATES_DIR)) {
+        let current = candidate_dir.join(constants::CURRENT_LINK);
+        if current.is_symlink() || current.exists() {
+            check_current_link(checkup, &current);
+        }
+    }
+}
+
+fn read_entries(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
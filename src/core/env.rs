@@ -1,27 +1,134 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use crate::constants;
+use crate::core::config::Config;
+use crate::utils::fs;
+
+/// Platform identifiers understood by the SDKMAN API.
+///
+/// `exotic` is what bash SDKMAN reports for unrecognized systems; the API
+/// only serves platform-independent candidates for it.
+pub const PLATFORMS: &[&str] = &[
+    "linuxx64",
+    "linuxarm64",
+    "linuxarm32hf",
+    "linuxarm32sf",
+    "linuxx32",
+    "linuxmusl",
+    "darwinx64",
+    "darwinarm64",
+    "windowsx64",
+    "exotic",
+];
 
 /// Detects the current platform and returns the SDKMAN API platform identifier.
 ///
 /// # Returns
 /// Platform string in SDKMAN format (e.g., "linuxx64", "darwinarm64")
 ///
+/// A `platform` set in the plugin settings replaces detection. On Apple
+/// Silicon, `sdkman_rosetta2_compatible=true` in `etc/config` selects
+/// x86_64 builds (`darwinx64`) as bash SDKMAN does.
+///
 /// # Errors
-/// Returns error if the OS/architecture combination is not supported
+/// Returns error if the configured platform is not a known identifier
 pub fn detect_platform() -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load();
+    if let Some(platform) = config.platform {
+        return validate_platform(&platform);
+    }
+    
     let os = std::env::consts::OS;
     let arch = std::env::consts::ARCH;
+    if (os, arch) == ("macos", "aarch64") && config.rosetta2_compatible {
+        return Ok("darwinx64".to_string());
+    }
     
+    Ok(platform_id(os, arch, is_musl(), is_hard_float()).to_string())
+}
+
+/// Resolves the platform to use for a command: an explicit `--platform`
+/// value if given, otherwise the detected one.
+///
+/// # Errors
+/// Returns error if the explicit platform is not a known identifier
+pub fn resolve_platform(explicit: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    match explicit {
+        Some(platform) => validate_platform(platform),
+        None => detect_platform(),
+    }
+}
+
+/// Normalizes a platform identifier and checks it against [`PLATFORMS`].
+///
+/// # Errors
+/// Returns error listing the supported identifiers if `platform` is unknown
+pub fn validate_platform(platform: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let normalized = platform.trim().to_lowercase();
+    if PLATFORMS.contains(&normalized.as_str()) {
+        Ok(normalized)
+    } else {
+        Err(format!("Unknown platform: {}. Supported: {}", platform, PLATFORMS.join(", ")).into())
+    }
+}
+
+/// Maps an OS/architecture pair to its SDKMAN platform identifier.
+///
+/// # Arguments
+/// * `os` - Value of `std::env::consts::OS`
+/// * `arch` - Value of `std::env::consts::ARCH`
+/// * `musl` - Whether the system uses musl libc (Alpine); only x86_64 has musl builds
+/// * `hard_float` - Whether 32-bit ARM uses the hard-float ABI
+pub fn platform_id(os: &str, arch: &str, musl: bool, hard_float: bool) -> &'static str {
     match (os, arch) {
-        ("linux", "x86_64") => Ok("linuxx64".to_string()),
-        ("linux", "aarch64") => Ok("linuxarm64".to_string()),
-        ("macos", "x86_64") => Ok("darwinx64".to_string()),
-        ("macos", "aarch64") => Ok("darwinarm64".to_string()),
-        ("windows", "x86_64") => Ok("windowsx64".to_string()),
-        _ => Err(format!("Unsupported platform: {} {}", os, arch).into()),
+        ("linux", "x86_64") if musl => "linuxmusl",
+        ("linux", "x86_64") => "linuxx64",
+        ("linux", "aarch64") => "linuxarm64",
+        ("linux", "arm") if hard_float => "linuxarm32hf",
+        ("linux", "arm") => "linuxarm32sf",
+        ("linux", "x86") => "linuxx32",
+        ("macos", "x86_64") => "darwinx64",
+        ("macos", "aarch64") => "darwinarm64",
+        ("windows", "x86_64") => "windowsx64",
+        _ => "exotic",
     }
 }
 
+/// Directories holding the system's dynamic loaders.
+const LOADER_DIRS: [&str; 2] = ["/lib", "/lib64"];
+
+/// Detects musl libc from the build target or the system's dynamic loaders.
+fn is_musl() -> bool {
+    cfg!(target_env = "musl") || is_musl_system(&LOADER_DIRS.map(Path::new))
+}
+
+/// Detects the ARM hard-float ABI from the presence of its dynamic loader.
+fn is_hard_float() -> bool {
+    has_loader(&LOADER_DIRS.map(Path::new), "ld-linux-armhf")
+}
+
+/// Returns true if the loaders in `lib_dirs` make this a musl system.
+///
+/// glibc distributions with the musl package installed ship a musl loader
+/// too, so it only counts when there is no glibc loader (`ld-linux-*`).
+pub fn is_musl_system(lib_dirs: &[&Path]) -> bool {
+    has_loader(lib_dirs, "ld-musl-") && !has_loader(lib_dirs, "ld-linux-")
+}
+
+fn has_loader(lib_dirs: &[&Path], prefix: &str) -> bool {
+    lib_dirs.iter().any(|dir| {
+        std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .any(|e| e.file_name().to_string_lossy().starts_with(prefix))
+            })
+            .unwrap_or(false)
+    })
+}
+
 /// Returns the SDKMAN installation directory path.
 ///
 /// Checks `SDKMAN_DIR` environment variable first (used for test isolation),
@@ -72,6 +179,30 @@ pub fn is_installed(candidate: &str, version: &str) -> bool {
     candidate_dir(candidate, version).map(|p| p.exists()).unwrap_or(false)
 }
 
+/// Returns the names of all candidates with a directory under `candidates/`.
+///
+/// # Returns
+/// Sorted vector of candidate names, or empty vector if none installed
+pub fn get_installed_candidates() -> Vec<String> {
+    let base = match candidates_dir() {
+        Ok(dir) => dir,
+        Err(_) => return vec![],
+    };
+    
+    let mut candidates: Vec<String> = std::fs::read_dir(&base)
+        .ok()
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    candidates.sort();
+    candidates
+}
+
 /// Returns a list of all installed versions for a candidate.
 ///
 /// # Arguments
@@ -102,6 +233,28 @@ pub fn get_installed_versions(candidate: &str) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// Returns where a local version (installed with `sdk install --from`) came from.
+///
+/// Linked versions report their target directory; extracted ones the URL or
+/// archive recorded in their source marker.
+///
+/// # Returns
+/// `None` for versions installed from the SDKMAN API
+pub fn local_version_source(candidate: &str, version: &str) -> Option<String> {
+    let dir = candidate_dir(candidate, version).ok()?;
+    if dir.is_symlink() {
+        return std::fs::read_link(&dir).ok().map(|p| p.display().to_string());
+    }
+    std::fs::read_to_string(dir.join(constants::LOCAL_SOURCE_MARKER))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// Checks whether a version was installed from a local source rather than the API.
+pub fn is_local_version(candidate: &str, version: &str) -> bool {
+    local_version_source(candidate, version).is_some()
+}
+
 /// Gets the currently active version for a candidate.
 ///
 /// On Unix: Reads the symlink target and extracts the version directory name.
@@ -113,7 +266,11 @@ pub fn get_installed_versions(candidate: &str) -> Vec<String> {
 /// # Returns
 /// Some(version) if a version is set, None otherwise
 pub fn get_current_version(candidate: &str) -> Option<String> {
-    let current = candidate_current(candidate).ok()?;
+    read_current(&candidate_current(candidate).ok()?)
+}
+
+/// Reads the version a `current` link or marker directory points to.
+pub fn read_current(current: &Path) -> Option<String> {
     if !current.exists() {
         return None;
     }
@@ -121,7 +278,7 @@ pub fn get_current_version(candidate: &str) -> Option<String> {
     #[cfg(unix)]
     {
         // On Unix: read the symlink and extract the version directory name
-        std::fs::read_link(&current)
+        std::fs::read_link(current)
             .ok()
             .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
     }
@@ -162,53 +319,317 @@ pub fn set_current_version(candidate: &str, version: &str) -> Result<(), Box<dyn
         return Err(format!("{} {} is not installed", candidate, version).into());
     }
     
+    link_current(&current, &target)
+}
+
+/// Points a `current` link at a version directory, replacing any existing link.
+///
+/// On Unix the symlink is relative (e.g. `17.0.9`) when the `relative_links`
+/// setting is on, absolute otherwise. On Windows a `current` directory with
+/// a `.version` marker is written instead.
+pub(crate) fn link_current(current: &Path, target: &Path) -> Result<(), Box<dyn std::error::Error>> {
     // Ensure parent directory exists
     if let Some(parent) = current.parent() {
         std::fs::create_dir_all(parent)?;
     }
     
-    if current.exists() {
-        std::fs::remove_dir_all(&current)?;
+    // Dangling links don't `exist` but still have to be replaced
+    if current.is_symlink() {
+        std::fs::remove_file(current)?;
+    } else if current.exists() {
+        std::fs::remove_dir_all(current)?;
     }
     
     #[cfg(unix)]
     {
-        std::os::unix::fs::symlink(&target, &current)?;
+        let link_target = if Config::load().relative_links {
+            // Only the parents are resolved: a linked version must stay the
+            // link's target rather than the directory it points to
+            let parent = current.parent().ok_or("current link has no parent directory")?;
+            let target_parent = target.parent().ok_or("version directory has no parent")?;
+            let version = target.file_name().ok_or("version directory has no name")?;
+            fs::relative_path(&parent.canonicalize()?, &target_parent.canonicalize()?.join(version))
+        } else {
+            target.to_path_buf()
+        };
+        std::os::unix::fs::symlink(link_target, current)?;
     }
     
     #[cfg(windows)]
     {
-        std::fs::create_dir_all(&current)?;
-        std::fs::write(current.join(constants::VERSION_MARKER), version)?;
+        let version = target.file_name().ok_or("version directory has no name")?;
+        std::fs::create_dir_all(current)?;
+        std::fs::write(current.join(constants::VERSION_MARKER), version.to_string_lossy().as_bytes())?;
     }
     
     Ok(())
 }
 
-/// Checks if current directory has a local SDKMAN environment.
-pub fn is_local_env() -> bool {
+/// Returns Nushell's working directory.
+///
+/// Uses `PWD` from the caller's environment rather than the plugin process
+/// directory, falling back to the process directory if `PWD` is unset.
+pub fn working_dir() -> Option<PathBuf> {
     std::env::var(constants::ENV_PWD)
         .ok()
-        .and_then(|p| std::path::PathBuf::from(p).canonicalize().ok())
+        .and_then(|p| PathBuf::from(p).canonicalize().ok())
         .or_else(|| std::env::current_dir().ok())
+}
+
+/// Checks if current directory has a local SDKMAN environment.
+pub fn is_local_env() -> bool {
+    working_dir()
         .map(|p| p.join(constants::SDKMAN_DIR_NAME).exists())
         .unwrap_or(false)
 }
 
 /// Returns the local SDKMAN directory path if it exists.
 pub fn local_sdkman_dir() -> Option<PathBuf> {
-    std::env::var(constants::ENV_PWD)
-        .ok()
-        .and_then(|p| std::path::PathBuf::from(p).canonicalize().ok())
-        .or_else(|| std::env::current_dir().ok())
+    working_dir()
         .map(|p| p.join(constants::SDKMAN_DIR_NAME))
         .filter(|p| p.exists())
 }
 
+/// Gets the version the local `.sdkman` environment points to for a candidate.
+///
+/// # Returns
+/// Some(version) if a local environment exists and links the candidate, None otherwise
+pub fn get_local_current_version(candidate: &str) -> Option<String> {
+    let local_current = local_sdkman_dir()?
+        .join(constants::CANDIDATES_DIR)
+        .join(candidate)
+        .join(constants::CURRENT_LINK);
+    read_current(&local_current)
+}
+
+/// Where the active version of a candidate is selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionSource {
+    /// The `current` link of the project's local `.sdkman`
+    Local,
+    /// The `current` link of `~/.sdkman`
+    Global,
+}
+
+impl fmt::Display for VersionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionSource::Local => write!(f, "local"),
+            VersionSource::Global => write!(f, "global"),
+        }
+    }
+}
+
+/// The version of a candidate in use in a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveVersion {
+    pub candidate: String,
+    pub version: String,
+    pub source: VersionSource,
+    /// Installation directory of the version.
+    pub home: PathBuf,
+}
+
+/// Resolves the version of a candidate in use in `project_dir`: the local
+/// `.sdkman` environment's `current` link first, then the global one.
+///
+/// # Returns
+/// None if neither links a version of the candidate
+pub fn active_version(candidate: &str, project_dir: &Path) -> Option<ActiveVersion> {
+    let local_dir = project_dir.join(constants::SDKMAN_DIR_NAME);
+    let local_current = local_dir.join(constants::CANDIDATES_DIR).join(candidate).join(constants::CURRENT_LINK);
+
+    if let Some(version) = read_current(&local_current) {
+        let isolated = local_candidate_dir(&local_dir, candidate, &version);
+        let home = if isolated.exists() { isolated } else { candidate_dir(candidate, &version).ok()? };
+        return Some(ActiveVersion { candidate: candidate.to_string(), version, source: VersionSource::Local, home });
+    }
+
+    let version = get_current_version(candidate)?;
+    let home = candidate_dir(candidate, &version).ok()?;
+    Some(ActiveVersion { candidate: candidate.to_string(), version, source: VersionSource::Global, home })
+}
+
+/// Returns the versions of a candidate usable in `project_dir`: those in
+/// `~/.sdkman` plus isolated installations in the project's `.sdkman`.
+///
+/// # Returns
+/// Sorted, deduplicated version strings
+pub fn installed_versions_in(candidate: &str, project_dir: &Path) -> Vec<String> {
+    let mut versions = get_installed_versions(candidate);
+    let local_dir = project_dir.join(constants::SDKMAN_DIR_NAME).join(constants::CANDIDATES_DIR).join(candidate);
+    if let Ok(entries) = std::fs::read_dir(local_dir) {
+        versions.extend(
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name() != constants::CURRENT_LINK && !e.path().is_symlink() && e.path().is_dir())
+                .filter_map(|e| e.file_name().into_string().ok()),
+        );
+    }
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+/// Returns the installation directory of a version as seen from
+/// `project_dir`: the project's isolated installation if any, else the one
+/// in `~/.sdkman`.
+///
+/// # Returns
+/// None if the version is installed in neither
+pub fn version_home(candidate: &str, version: &str, project_dir: &Path) -> Option<PathBuf> {
+    let isolated = local_candidate_dir(&project_dir.join(constants::SDKMAN_DIR_NAME), candidate, version);
+    if isolated.exists() {
+        return Some(isolated);
+    }
+    candidate_dir(candidate, version).ok().filter(|dir| dir.exists())
+}
+
+/// Returns the active versions of all candidates in `project_dir`, sorted
+/// by candidate.
+pub fn active_versions(project_dir: &Path) -> Vec<ActiveVersion> {
+    let mut candidates = get_installed_candidates();
+    let local_candidates = project_dir.join(constants::SDKMAN_DIR_NAME).join(constants::CANDIDATES_DIR);
+    if let Ok(entries) = std::fs::read_dir(local_candidates) {
+        candidates.extend(entries.filter_map(|e| e.ok()).filter_map(|e| e.file_name().into_string().ok()));
+    }
+    candidates.sort();
+    candidates.dedup();
+    candidates.iter().filter_map(|c| active_version(c, project_dir)).collect()
+}
+
+/// A candidate's active version compared with the nearest `.sdkmanrc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinStatus {
+    pub candidate: String,
+    pub active: Option<ActiveVersion>,
+    /// Version pinned in the nearest `.sdkmanrc`.
+    pub pinned: Option<String>,
+}
+
+impl PinStatus {
+    /// True when nothing is pinned or the pinned version is the one in use.
+    pub fn matches_pin(&self) -> bool {
+        match &self.pinned {
+            Some(pinned) => self.active.as_ref().is_some_and(|a| &a.version == pinned),
+            None => true,
+        }
+    }
+}
+
+/// Compares the active version of a candidate in `start` with its pin.
+///
+/// The local `.sdkman` is the one next to the nearest `.sdkmanrc`, so a
+/// subdirectory of a project reports the project's versions.
+pub fn pin_status(candidate: &str, start: &Path) -> PinStatus {
+    let project_dir = project_root(start);
+    PinStatus {
+        candidate: candidate.to_string(),
+        active: active_version(candidate, &project_dir),
+        pinned: nearest_pins(start).remove(candidate),
+    }
+}
+
+/// Compares every active or pinned candidate with its pin, sorted by
+/// candidate (see [`pin_status`]).
+pub fn pin_statuses(start: &Path) -> Vec<PinStatus> {
+    let project_dir = project_root(start);
+    let mut pins = nearest_pins(start);
+    let mut statuses: Vec<PinStatus> = active_versions(&project_dir)
+        .into_iter()
+        .map(|active| PinStatus { candidate: active.candidate.clone(), pinned: pins.remove(&active.candidate), active: Some(active) })
+        .collect();
+
+    // Pinned candidates without any version in use are out of sync too
+    statuses.extend(pins.into_iter().map(|(candidate, pinned)| PinStatus { candidate, active: None, pinned: Some(pinned) }));
+    statuses.sort_by(|a, b| a.candidate.cmp(&b.candidate));
+    statuses
+}
+
+fn nearest_pins(start: &Path) -> HashMap<String, String> {
+    find_sdkmanrc(start)
+        .and_then(|rc| read_sdkmanrc(&rc).ok())
+        .unwrap_or_default()
+}
+
+/// Returns when a candidate version was installed.
+///
+/// Uses the modification time of the version directory (or link), which
+/// is set when the installation is moved into place.
+pub fn installed_at(candidate: &str, version: &str) -> Option<SystemTime> {
+    let dir = candidate_dir(candidate, version).ok()?;
+    std::fs::symlink_metadata(dir).ok()?.modified().ok()
+}
+
+/// Returns the `.sdkmanrc` in the working directory, if any.
+pub fn working_sdkmanrc() -> Option<PathBuf> {
+    working_dir()
+        .map(|dir| dir.join(constants::SDKMAN_RC_FILE))
+        .filter(|rc| rc.exists())
+}
+
+/// Returns the nearest `.sdkmanrc` in `start` or one of its ancestors.
+pub fn find_sdkmanrc(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(constants::SDKMAN_RC_FILE))
+        .find(|rc| rc.is_file())
+}
+
+/// Returns the project directory for `start`: the directory of the nearest
+/// `.sdkmanrc`, or `start` itself if there is none.
+///
+/// The project's isolated `.sdkman` lives next to its `.sdkmanrc`, so
+/// commands run from a subdirectory resolve the same versions.
+pub fn project_root(start: &Path) -> PathBuf {
+    find_sdkmanrc(start)
+        .and_then(|rc| rc.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| start.to_path_buf())
+}
+
+/// Parses `.sdkmanrc` content into candidate/version pairs.
+///
+/// Blank lines and `#` comments are skipped; lines without `=` are ignored.
+pub fn parse_sdkmanrc(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Reads and parses an `.sdkmanrc` file.
+///
+/// # Errors
+/// Returns error if the file cannot be read
+pub fn read_sdkmanrc(path: &Path) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(parse_sdkmanrc(&content))
+}
+
+/// Returns the isolated installation directory of a version in a local
+/// environment (`.sdkman/candidates/<candidate>/<version>`).
+pub fn local_candidate_dir(local_dir: &Path, candidate: &str, version: &str) -> PathBuf {
+    local_dir.join(constants::CANDIDATES_DIR).join(candidate).join(version)
+}
+
+/// Checks if a version is installed in the local environment itself
+/// (isolated mode) rather than in `~/.sdkman`.
+pub fn is_installed_locally(candidate: &str, version: &str) -> bool {
+    local_sdkman_dir()
+        .map(|dir| local_candidate_dir(&dir, candidate, version).exists())
+        .unwrap_or(false)
+}
+
 /// Sets the current version in a local environment.
 ///
 /// Creates a symlink in `.sdkman/candidates/<candidate>/current` that points
-/// to the global installation at `~/.sdkman/candidates/<candidate>/<version>`.
+/// to the isolated installation in `.sdkman/candidates/<candidate>/<version>`
+/// if there is one, otherwise to the global installation at
+/// `~/.sdkman/candidates/<candidate>/<version>`.
 ///
 /// # Arguments
 /// * `candidate` - Candidate name (e.g., "java")
@@ -223,34 +644,24 @@ pub fn set_local_current_version(candidate: &str, version: &str) -> Result<(), B
     let local_dir = local_sdkman_dir()
         .ok_or("No local .sdkman directory found")?;
     
-    // Target points to global installation
-    let global_target = candidate_dir(candidate, version)?;
-    if !global_target.exists() {
+    // Target points to the isolated installation, else the global one
+    let isolated_target = local_candidate_dir(&local_dir, candidate, version);
+    let target = if isolated_target.exists() {
+        isolated_target
+    } else {
+        candidate_dir(candidate, version)?
+    };
+    if !target.exists() {
         return Err(format!("{} {} is not installed", candidate, version).into());
     }
     
     // Current symlink is in local directory
     let local_current = local_dir.join(constants::CANDIDATES_DIR).join(candidate).join(constants::CURRENT_LINK);
     
-    // Ensure parent directory exists
-    if let Some(parent) = local_current.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
+    link_current(&local_current, &target)?;
     
-    if local_current.exists() {
-        std::fs::remove_dir_all(&local_current)?;
+    if let Some(project_dir) = local_dir.parent() {
+        crate::core::activation::refresh_local_scripts(project_dir);
     }
-    
-    #[cfg(unix)]
-    {
-        std::os::unix::fs::symlink(&global_target, &local_current)?;
-    }
-    
-    #[cfg(windows)]
-    {
-        std::fs::create_dir_all(&local_current)?;
-        std::fs::write(local_current.join(constants::VERSION_MARKER), version)?;
-    }
-    
     Ok(())
 }
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use crate::constants;
+use crate::core::activation::Activation;
+use crate::core::{env, which};
+
+/// A candidate version selected for `sdk exec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainEntry {
+    pub candidate: String,
+    pub version: String,
+    /// Installation directory used as the candidate's home.
+    pub home: PathBuf,
+}
+
+/// Resolves the versions to run a command with.
+///
+/// Starts from the pins of the nearest `.sdkmanrc` (if any); `specs`
+/// (`candidate@version`, or a bare candidate for its active version, local
+/// link first) are added on top and win over pins of the same candidate.
+/// Versions installed in the project's isolated `.sdkman` are preferred
+/// over `~/.sdkman`.
+///
+/// # Errors
+/// Returns error if a version is not installed or a bare candidate has no
+/// current version
+pub fn resolve_toolchain(specs: &[String], project_dir: &Path) -> Result<Vec<ToolchainEntry>, Box<dyn Error>> {
+    let mut versions: Vec<(String, String)> = env::find_sdkmanrc(project_dir)
+        .and_then(|rc| env::read_sdkmanrc(&rc).ok())
+        .map(|pins| pins.into_iter().collect())
+        .unwrap_or_default();
+    versions.sort();
+
+    let project_dir = env::project_root(project_dir);
+
+    for spec in specs {
+        let (candidate, version) = match spec.split_once('@') {
+            Some((candidate, version)) => (candidate.to_string(), version.to_string()),
+            None => {
+                let version = env::active_version(spec, &project_dir)
+                    .map(|active| active.version)
+                    .ok_or_else(|| format!("{} has no current version; use {}@<version>", spec, spec))?;
+                (spec.clone(), version)
+            }
+        };
+        versions.retain(|(c, _)| *c != candidate);
+        versions.push((candidate, version));
+    }
+
+    let local_dir = project_dir.join(constants::SDKMAN_DIR_NAME);
+    versions
+        .into_iter()
+        .map(|(candidate, version)| {
+            let isolated = env::local_candidate_dir(&local_dir, &candidate, &version);
+            let home = if isolated.exists() { isolated } else { env::candidate_dir(&candidate, &version)? };
+            if !home.exists() {
+                return Err(format!("{} {} is not installed (run: sdk install {} {})", candidate, version, candidate, version).into());
+            }
+            Ok(ToolchainEntry { candidate, version, home })
+        })
+        .collect()
+}
+
+/// Builds the environment of the command: `base` with the toolchain's `bin`
+/// directories prepended to PATH and its home variables set.
+pub fn command_env(toolchain: &[ToolchainEntry], base: &HashMap<String, String>) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let homes: Vec<(String, PathBuf)> = toolchain.iter().map(|t| (t.candidate.clone(), t.home.clone())).collect();
+    let activation = Activation::for_homes(&homes);
+
+    let mut vars = base.clone();
+    // Windows spells it `Path`; keep whichever name the environment uses
+    let path_key = vars.keys().find(|k| k.eq_ignore_ascii_case("PATH")).cloned().unwrap_or_else(|| "PATH".into());
+    let existing = vars.get(&path_key).map(std::env::split_paths).into_iter().flatten();
+    let path = std::env::join_paths(activation.path_entries.iter().cloned().chain(existing))?;
+    vars.insert(path_key, path.to_string_lossy().into_owned());
+    vars.extend(activation.vars);
+    Ok(vars)
+}
+
+/// Runs `program` with `args` in `cwd` with exactly the environment `vars`.
+///
+/// stderr is inherited; stdout is returned as a reader. `stdin` is copied
+/// to the command on a background thread (no input if `None`).
+///
+/// # Errors
+/// Returns error if the program cannot be started
+pub fn spawn(
+    program: &str,
+    args: &[String],
+    cwd: &Path,
+    vars: &HashMap<String, String>,
+    stdin: Option<Box<dyn Read + Send>>,
+) -> Result<ExecOutput, Box<dyn Error>> {
+    // Resolve the program on the toolchain's PATH rather than the plugin's
+    let path = vars.iter().find(|(k, _)| k.eq_ignore_ascii_case("PATH")).map(|(_, v)| v.as_str());
+    let resolved = which(program, path, cwd).unwrap_or_else(|| PathBuf::from(program));
+
+    let mut child = Command::new(&resolved)
+        .args(args)
+        .current_dir(cwd)
+        .env_clear()
+        .envs(vars)
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if let (Some(mut input), Some(mut child_stdin)) = (stdin, child.stdin.take()) {
+        std::thread::spawn(move || std::io::copy(&mut input, &mut child_stdin).ok());
+    }
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    Ok(ExecOutput { program: program.to_string(), child, stdout, finished: false })
+}
+
+/// Finds an executable in a PATH string, like the shell would.
+pub fn which(program: &str, path: Option<&str>, cwd: &Path) -> Option<PathBuf> {
+    if program.contains(std::path::MAIN_SEPARATOR) || program.contains('/') {
+        return Some(cwd.join(program));
+    }
+    std::env::split_paths(path?).find_map(|dir| which::executable_in(&dir, program))
+}
+
+/// stdout of a command started by [`spawn`].
+///
+/// Reads until the command closes stdout, then waits for it; a non-zero
+/// exit status surfaces as a read error naming the exit code.
+pub struct ExecOutput {
+    program: String,
+    child: Child,
+    stdout: ChildStdout,
+    finished: bool,
+}
+
+impl Read for ExecOutput {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.stdout.read(buf)?;
+        if n == 0 && !buf.is_empty() && !self.finished {
+            self.finished = true;
+            let status = self.child.wait()?;
+            if !status.success() {
+                let reason = match status.code() {
+                    Some(code) => format!("exited with code {}", code),
+                    None => "was terminated by a signal".to_string(),
+                };
+                return Err(std::io::Error::other(format!("{} {}", self.program, reason)));
+            }
+        }
+        Ok(n)
+    }
+}
@@ -0,0 +1,152 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::constants;
+use crate::core::{env, install};
+use crate::utils::archive;
+
+/// Output of a post-install hook that ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookReport {
+    /// Built-in fixup name or user script file name.
+    pub name: String,
+    pub output: String,
+}
+
+impl std::fmt::Display for HookReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.output.is_empty() {
+            write!(f, "[hook {}]", self.name)
+        } else {
+            write!(f, "[hook {}] {}", self.name, self.output)
+        }
+    }
+}
+
+/// Returns the directory holding user hooks (`~/.sdkman/etc/hooks`).
+pub fn hooks_dir() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(env::sdkman_dir()?.join(constants::ETC_DIR).join(constants::HOOKS_DIR))
+}
+
+/// Runs the built-in layout fixups and then the user hooks of a candidate
+/// on a freshly extracted installation.
+///
+/// User hooks are the files in `~/.sdkman/etc/hooks/<candidate>/`, run in
+/// name order inside the installation directory: `.nu` files with `nu`,
+/// `.sh` files with `sh`, anything else directly. They receive
+/// `SDKMAN_CANDIDATE`, `SDKMAN_VERSION`, `SDKMAN_PLATFORM` and
+/// `SDKMAN_CANDIDATE_DIR` in their environment.
+///
+/// # Returns
+/// Reports of the fixups that applied and the hooks that ran
+///
+/// # Errors
+/// Returns error if a fixup fails or a hook exits unsuccessfully
+pub fn run_post_install(candidate: &str, version: &str, platform: &str, install_dir: &Path) -> Result<Vec<HookReport>, Box<dyn Error>> {
+    let mut reports = builtin_fixups(candidate, install_dir)?;
+
+    let dir = hooks_dir()?.join(candidate);
+    let mut scripts: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_file()).collect())
+        .unwrap_or_default();
+    scripts.sort();
+
+    for script in scripts {
+        reports.push(run_script(&script, candidate, version, platform, install_dir)?);
+    }
+    Ok(reports)
+}
+
+/// Applies the built-in layout fixups that match the installation.
+///
+/// - macOS JDK bundles: moves `Contents/Home` to the version root
+/// - Nested archives: extracts a `.zip`, `.tar.gz` or `.tgz` file that is
+///   the only file of the installation
+///
+/// # Errors
+/// Returns error if a fixup applies but cannot be completed
+pub fn builtin_fixups(candidate: &str, install_dir: &Path) -> Result<Vec<HookReport>, Box<dyn Error>> {
+    let mut reports = Vec::new();
+
+    if let Some(nested) = nested_archive(install_dir) {
+        let name = nested.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let staging = sibling(install_dir, "nested");
+        archive::extract(&nested, &staging)?;
+        install::move_and_normalize(&staging, install_dir)?;
+        std::fs::remove_dir_all(&staging).ok();
+        reports.push(HookReport { name: "nested-archive".into(), output: format!("Extracted nested archive {}", name) });
+    }
+
+    let bundle_home = install_dir.join("Contents").join("Home");
+    if candidate == "java" && bundle_home.is_dir() && !install_dir.join("bin").exists() {
+        let staging = sibling(install_dir, "bundle");
+        std::fs::rename(install_dir, &staging)?;
+        std::fs::rename(staging.join("Contents").join("Home"), install_dir)?;
+        std::fs::remove_dir_all(&staging).ok();
+        reports.push(HookReport { name: "macos-jdk-layout".into(), output: "Moved Contents/Home to the version root".into() });
+    }
+
+    Ok(reports)
+}
+
+/// Staging path next to the installation (`<version>.<suffix>`); version
+/// names contain dots, so `Path::with_extension` would mangle them.
+fn sibling(install_dir: &Path, suffix: &str) -> PathBuf {
+    let mut name = install_dir.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", suffix));
+    install_dir.with_file_name(name)
+}
+
+/// Matched by extension only: single-file candidates such as `.jar`
+/// distributions are zip files too and must be left alone.
+fn nested_archive(install_dir: &Path) -> Option<PathBuf> {
+    let entries: Vec<PathBuf> = std::fs::read_dir(install_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.file_name().is_some_and(|n| n != constants::LOCAL_SOURCE_MARKER))
+        .collect();
+    match entries.as_slice() {
+        [single] if single.is_file() && is_archive_name(single) => Some(single.clone()),
+        _ => None,
+    }
+}
+
+fn is_archive_name(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    [".zip", ".tar.gz", ".tgz"].iter().any(|ext| name.ends_with(ext))
+}
+
+fn run_script(script: &Path, candidate: &str, version: &str, platform: &str, install_dir: &Path) -> Result<HookReport, Box<dyn Error>> {
+    let name = script.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut command = match script.extension().and_then(|e| e.to_str()) {
+        Some("nu") => {
+            let mut c = Command::new("nu");
+            c.arg(script);
+            c
+        }
+        Some("sh") => {
+            let mut c = Command::new("sh");
+            c.arg(script);
+            c
+        }
+        _ => Command::new(script),
+    };
+
+    let output = command
+        .current_dir(install_dir)
+        .env("SDKMAN_CANDIDATE", candidate)
+        .env("SDKMAN_VERSION", version)
+        .env("SDKMAN_PLATFORM", platform)
+        .env("SDKMAN_CANDIDATE_DIR", install_dir)
+        .output()
+        .map_err(|e| format!("Failed to run hook {}: {}", name, e))?;
+
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr))
+        .trim()
+        .to_string();
+    if !output.status.success() {
+        return Err(format!("Hook {} failed ({}): {}", name, output.status, text).into());
+    }
+    Ok(HookReport { name, output: text })
+}
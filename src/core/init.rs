@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use crate::constants;
+use crate::core::config::Config;
+use crate::core::env;
+
+/// First line of every generated init module; only files starting with it
+/// are regenerated automatically.
+pub const GENERATED_HEADER: &str = "# SDKMAN! Nushell initialization, generated by `sdk init`";
+
+/// An installed candidate exposed by the init module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitCandidate {
+    pub candidate: String,
+    /// Variable pointing at the candidate's `current` directory (e.g. `JAVA_HOME`).
+    pub home_var: String,
+}
+
+/// Everything the Nushell init module is generated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitModule {
+    pub sdkman_dir: PathBuf,
+    pub candidates: Vec<InitCandidate>,
+    /// Install the `.sdkmanrc` auto-env hook.
+    pub auto_env: bool,
+}
+
+impl InitModule {
+    /// Collects the SDKMAN directory, installed candidates and settings.
+    ///
+    /// # Errors
+    /// Returns error if the SDKMAN directory cannot be determined
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let config = Config::load();
+        Ok(InitModule {
+            sdkman_dir: env::sdkman_dir()?,
+            candidates: env::get_installed_candidates()
+                .into_iter()
+                .map(|candidate| InitCandidate { home_var: config.home_var(&candidate), candidate })
+                .collect(),
+            auto_env: config.auto_env,
+        })
+    }
+
+    /// Renders the module source.
+    pub fn render(&self) -> String {
+        let candidates_dir = self.sdkman_dir.join(constants::CANDIDATES_DIR);
+        let current = |candidate: &str| candidates_dir.join(candidate).join(constants::CURRENT_LINK);
+
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push('\n');
+        out.push_str("# Regenerated when candidates are installed; run `sdk init --write` after changing settings.\n\n");
+
+        out.push_str("export-env {\n");
+        out.push_str(&format!("    $env.SDKMAN_DIR = {}\n", nu_string(&self.sdkman_dir)));
+        out.push_str(&format!("    $env.SDKMAN_CANDIDATES_DIR = {}\n", nu_string(&candidates_dir)));
+
+        out.push_str("\n    let candidate_bins = [\n");
+        for c in &self.candidates {
+            out.push_str(&format!("        {}\n", nu_string(&current(&c.candidate).join("bin"))));
+        }
+        out.push_str("    ] | where {|dir| $dir | path exists }\n");
+        out.push_str("    $env.PATH = ($env.PATH | split row (char esep) | prepend $candidate_bins | uniq)\n");
+
+        if !self.candidates.is_empty() {
+            out.push('\n');
+        }
+        for c in &self.candidates {
+            out.push_str(&format!("    $env.{} = {}\n", c.home_var, nu_string(&current(&c.candidate))));
+        }
+
+        if self.auto_env {
+            out.push_str(concat!(
+                "\n    # Switch to the versions pinned in .sdkmanrc when entering a directory\n",
+                "    $env.config = ($env.config | upsert hooks.env_change.PWD {|config|\n",
+                "        $config.hooks?.env_change?.PWD? | default [] | append {|before, after|\n",
+                "            if ($after | path join \".sdkmanrc\" | path exists) { sdk env | print }\n",
+                "        }\n",
+                "    })\n",
+            ));
+        }
+        out.push_str("}\n");
+
+        out.push_str(concat!(
+            "\n# Completers for custom commands wrapping `sdk`\n",
+            "export def \"nu-complete sdk candidates\" [] {\n",
+            "    ls $env.SDKMAN_CANDIDATES_DIR | where type == dir | get name | path basename\n",
+            "}\n",
+            "\n",
+            "export def \"nu-complete sdk versions\" [context: string] {\n",
+            "    let candidate = ($context | split row ' ' | where $it != '' | get 2? | default '')\n",
+            "    let dir = ($env.SDKMAN_CANDIDATES_DIR | path join $candidate)\n",
+            "    if ($candidate | is-empty) or not ($dir | path exists) { return [] }\n",
+            "    ls $dir | get name | path basename | where $it != 'current'\n",
+            "}\n",
+        ));
+        out
+    }
+}
+
+/// Returns the path of the init module (`~/.sdkman/bin/sdkman-init.nu`).
+pub fn init_script_path() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(env::sdkman_dir()?.join(constants::BIN_DIR).join(constants::INIT_SCRIPT_NU))
+}
+
+/// Generates the init module and writes it to `path`.
+///
+/// # Errors
+/// Returns error if the file cannot be written
+pub fn write_init_script(path: &Path) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, InitModule::load()?.render())?;
+    Ok(())
+}
+
+/// Regenerates the init module if it exists and was generated by `sdk init`.
+///
+/// Called after installs so new candidates show up in PATH in the next
+/// shell; hand-written init scripts are never overwritten.
+pub fn refresh_init_script() {
+    let Ok(path) = init_script_path() else { return };
+    let generated = std::fs::read_to_string(&path).is_ok_and(|c| c.starts_with(GENERATED_HEADER));
+    if generated {
+        write_init_script(&path).ok();
+    }
+}
+
+/// Quotes a path as a Nushell raw string so backslashes are kept verbatim.
+fn nu_string(path: &Path) -> String {
+    format!("r#'{}'#", path.display())
+}
@@ -1,58 +1,244 @@
 use std::error::Error;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use crate::constants;
 use crate::core::{api, env};
+use crate::core::archives::ArchiveCache;
+use crate::core::hooks::{self, HookReport};
+use crate::core::{init, shims};
 use crate::utils::{download, archive};
 
-pub fn install_candidate(candidate: &str, version: &str, platform: &str) -> Result<(), Box<dyn Error>> {
-    let download_url = api::get_download_url(candidate, version, platform);
-    
-    // Create a unique temp directory for this installation
-    let temp_base = std::env::temp_dir().join(format!("sdkman-install-{}-{}", candidate, version));
-    std::fs::create_dir_all(&temp_base)?;
-    
-    let archive_name = format!("{}-{}{}", candidate, version, if cfg!(windows) { ".zip" } else { ".tar.gz" });
-    let archive_path = temp_base.join(&archive_name);
+/// Prefixes of the staging directories in `$SDKMAN_DIR/tmp`.
+pub(crate) const INSTALL_TEMP_PREFIX: &str = "sdkman-install-";
+pub(crate) const LOCAL_TEMP_PREFIX: &str = "sdkman-local-";
+
+/// Downloads and installs a candidate version.
+///
+/// Archives are taken from the archive cache when available; fresh
+/// downloads are added to it. Post-install hooks run after extraction.
+///
+/// # Returns
+/// Reports of the post-install hooks that ran
+pub fn install_candidate(candidate: &str, version: &str, platform: &str) -> Result<Vec<HookReport>, Box<dyn Error>> {
+    install_candidate_into(candidate, version, platform, &env::candidate_dir(candidate, version)?)
+}
+
+/// Downloads and installs a candidate version into `install_dir`, e.g. a
+/// project's isolated `.sdkman/candidates/<candidate>/<version>`.
+///
+/// # Returns
+/// Reports of the post-install hooks that ran
+pub fn install_candidate_into(candidate: &str, version: &str, platform: &str, install_dir: &Path) -> Result<Vec<HookReport>, Box<dyn Error>> {
+    let reports = install_candidate_deferred(candidate, version, platform, install_dir)?;
+    refresh_after_install();
+    Ok(reports)
+}
+
+/// Like [`install_candidate_into`] but leaves the init script and shims
+/// alone, for installs running in parallel; call [`refresh_after_install`]
+/// once they are all done.
+pub(crate) fn install_candidate_deferred(candidate: &str, version: &str, platform: &str, install_dir: &Path) -> Result<Vec<HookReport>, Box<dyn Error>> {
+    // Create a unique temp directory for this installation; removed on drop,
+    // including when a step below fails
+    let temp_base = install_temp_dir(INSTALL_TEMP_PREFIX, candidate, version)?;
     
-    // Download
-    download::download_file(&download_url, &archive_path)?;
+    let cache = ArchiveCache::open()?;
+    let archive_path = fetch_archive(&cache, candidate, version, platform, temp_base.path())?;
     
     // Extract to a 'source' subdirectory in temp
-    let extract_dir = temp_base.join("source");
+    let extract_dir = temp_base.path().join("source");
     archive::extract(&archive_path, &extract_dir)?;
     
-    // Determine final installation path
-    let install_dir = env::candidate_dir(candidate, version)?;
-    
     // Move and normalize
-    move_and_normalize(&extract_dir, &install_dir)?;
+    move_and_normalize(&extract_dir, install_dir)?;
+    drop(temp_base);
     
-    // Cleanup
-    std::fs::remove_dir_all(&temp_base).ok();
-    
-    Ok(())
+    run_post_install_hooks(candidate, version, platform, install_dir)
 }
 
-pub fn install_local(candidate: &str, version: &str, local_path: &Path) -> Result<(), Box<dyn Error>> {
+/// Regenerates what depends on the installed versions: the init script and,
+/// if in use, the shims.
+pub(crate) fn refresh_after_install() {
+    init::refresh_init_script();
+    shims::refresh();
+}
+
+/// Downloads the archive of a version into the archive cache without
+/// installing it, e.g. to prepare builds for another platform.
+///
+/// # Returns
+/// Path of the cached archive
+///
+/// # Errors
+/// Returns error if the archive cache is disabled or the download fails
+pub fn download_to_cache(candidate: &str, version: &str, platform: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let cache = ArchiveCache::open()?;
+    if !cache.is_enabled() {
+        return Err("The archive cache is disabled (archive_cache_size is 0)".into());
+    }
+    let temp_base = install_temp_dir(INSTALL_TEMP_PREFIX, candidate, version)?;
+    fetch_archive(&cache, candidate, version, platform, temp_base.path())
+}
+
+/// Returns the cached archive of a version, downloading it into `temp_base`
+/// and storing it in the cache if needed.
+fn fetch_archive(cache: &ArchiveCache, candidate: &str, version: &str, platform: &str, temp_base: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    if let Some(cached) = cache.lookup(candidate, version, platform) {
+        return Ok(cached);
+    }
+    let download_url = api::get_download_url(candidate, version, platform);
+    let archive_name = format!("{}-{}{}", candidate, version, if platform.starts_with("windows") { ".zip" } else { ".tar.gz" });
+    let downloaded = temp_base.join(&archive_name);
+    download::download_file(&download_url, &downloaded)?;
+    cache.store(candidate, version, platform, &downloaded)
+}
+
+/// Where `sdk install --from` takes a version from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallSource {
+    /// Archive downloaded over HTTP(S).
+    Url(String),
+    /// Existing installation, linked rather than copied.
+    Directory(PathBuf),
+    /// Local tar.gz or zip archive.
+    Archive(PathBuf),
+}
+
+impl InstallSource {
+    /// Classifies a `--from` argument as URL, directory or archive.
+    ///
+    /// # Arguments
+    /// * `from` - URL or path
+    /// * `base_dir` - Directory relative paths are resolved against (Nushell's
+    ///   working directory, not the plugin process's)
+    ///
+    /// # Errors
+    /// Returns error if the argument is neither a URL nor an existing path
+    pub fn parse(from: &str, base_dir: &Path) -> Result<Self, Box<dyn Error>> {
+        if from.starts_with("http://") || from.starts_with("https://") {
+            return Ok(InstallSource::Url(from.to_string()));
+        }
+        
+        let path = base_dir.join(from);
+        if path.is_dir() {
+            Ok(InstallSource::Directory(path.canonicalize()?))
+        } else if path.is_file() {
+            Ok(InstallSource::Archive(path.canonicalize()?))
+        } else {
+            Err(format!("Local file not found: {}", path.display()).into())
+        }
+    }
+}
+
+impl std::fmt::Display for InstallSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstallSource::Url(url) => write!(f, "{}", url),
+            InstallSource::Directory(path) | InstallSource::Archive(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Installs a version from an archive file.
+pub fn install_local(candidate: &str, version: &str, local_path: &Path) -> Result<Vec<HookReport>, Box<dyn Error>> {
     if !local_path.exists() {
         return Err(format!("Local file not found: {}", local_path.display()).into());
     }
-    
-    let temp_base = std::env::temp_dir().join(format!("sdkman-local-{}-{}", candidate, version));
-    std::fs::create_dir_all(&temp_base)?;
-    
-    let extract_dir = temp_base.join("source");
-    archive::extract(local_path, &extract_dir)?;
-    
+    install_from(candidate, version, &InstallSource::Archive(local_path.to_path_buf()))
+}
+
+/// Installs a version from a URL, directory or archive and marks it local.
+///
+/// Directories are symlinked into the candidates directory (copied on
+/// Windows, where symlinks need extra privileges); archives, including
+/// downloaded ones, are extracted like regular installs. Local versions are
+/// reported as such and never replaced by `sdk upgrade`. Post-install hooks
+/// run on extracted archives but not on linked directories.
+///
+/// # Returns
+/// Reports of the post-install hooks that ran
+///
+/// # Errors
+/// Returns error if the source cannot be read, downloaded or extracted
+pub fn install_from(candidate: &str, version: &str, source: &InstallSource) -> Result<Vec<HookReport>, Box<dyn Error>> {
     let install_dir = env::candidate_dir(candidate, version)?;
     
-    move_and_normalize(&extract_dir, &install_dir)?;
-    
-    std::fs::remove_dir_all(&temp_base).ok();
+    match source {
+        InstallSource::Directory(dir) => {
+            link_local_dir(dir, &install_dir, source)?;
+            refresh_after_install();
+            return Ok(Vec::new());
+        }
+        InstallSource::Archive(path) => {
+            let temp_base = install_temp_dir(LOCAL_TEMP_PREFIX, candidate, version)?;
+            extract_local(path, temp_base.path(), &install_dir)?;
+        }
+        InstallSource::Url(url) => {
+            let temp_base = install_temp_dir(LOCAL_TEMP_PREFIX, candidate, version)?;
+            let name = url.rsplit('/').next().filter(|n| !n.is_empty()).unwrap_or("archive");
+            let downloaded = temp_base.path().join(name);
+            download::download_file(url, &downloaded)?;
+            extract_local(&downloaded, temp_base.path(), &install_dir)?;
+        }
+    }
     
+    let platform = env::detect_platform()?;
+    let reports = run_post_install_hooks(candidate, version, &platform, &install_dir)?;
+    std::fs::write(install_dir.join(constants::LOCAL_SOURCE_MARKER), source.to_string())?;
+    refresh_after_install();
+    Ok(reports)
+}
+
+/// Creates a fresh staging directory for an install under `$SDKMAN_DIR/tmp`,
+/// on the same filesystem as the candidates.
+///
+/// The name is unique per install, so a retry never reuses a previous
+/// attempt's files; the directory is removed when dropped. Only directories
+/// left by a killed process remain, for `sdk doctor --fix` or `sdk flush tmp`.
+fn install_temp_dir(prefix: &str, candidate: &str, version: &str) -> Result<TempDir, Box<dyn Error>> {
+    let tmp = env::sdkman_dir()?.join(constants::TMP_DIR);
+    std::fs::create_dir_all(&tmp)?;
+    Ok(tempfile::Builder::new()
+        .prefix(&format!("{}{}-{}-", prefix, candidate, version))
+        .tempdir_in(tmp)?)
+}
+
+/// Runs the post-install hooks, removing the installation if one fails so
+/// a half-configured version isn't left behind.
+fn run_post_install_hooks(candidate: &str, version: &str, platform: &str, install_dir: &Path) -> Result<Vec<HookReport>, Box<dyn Error>> {
+    hooks::run_post_install(candidate, version, platform, install_dir).inspect_err(|_| {
+        std::fs::remove_dir_all(install_dir).ok();
+    })
+}
+
+fn extract_local(archive_path: &Path, temp_base: &Path, install_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let extract_dir = temp_base.join("source");
+    archive::extract(archive_path, &extract_dir)?;
+    move_and_normalize(&extract_dir, install_dir)
+}
+
+#[cfg(unix)]
+fn link_local_dir(dir: &Path, install_dir: &Path, _source: &InstallSource) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = install_dir.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::os::unix::fs::symlink(dir, install_dir)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn link_local_dir(dir: &Path, install_dir: &Path, source: &InstallSource) -> Result<(), Box<dyn Error>> {
+    copy_dir_recursive(dir, install_dir)?;
+    std::fs::write(install_dir.join(constants::LOCAL_SOURCE_MARKER), source.to_string())?;
     Ok(())
 }
 
-fn move_and_normalize(source: &Path, destination: &Path) -> Result<(), Box<dyn Error>> {
+/// Moves extracted contents into `destination`, replacing any existing
+/// installation.
+///
+/// If `source` holds a single directory (the usual archive layout), that
+/// directory's contents are installed instead of the wrapper.
+pub(crate) fn move_and_normalize(source: &Path, destination: &Path) -> Result<(), Box<dyn Error>> {
     // Check if source contains a single directory
     let entries: Vec<_> = std::fs::read_dir(source)?
         .filter_map(|e| e.ok())
@@ -85,7 +271,9 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
     for entry in std::fs::read_dir(src)? {
         let entry = entry?;
         let ty = entry.file_type()?;
-        if ty.is_dir() {
+        if ty.is_symlink() {
+            copy_symlink(&entry.path(), &dst.join(entry.file_name()))?;
+        } else if ty.is_dir() {
             copy_dir_recursive(&entry.path(), &dst.join(entry.file_name()))?;
         } else {
             std::fs::copy(entry.path(), dst.join(entry.file_name()))?;
@@ -93,3 +281,13 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(src)?, dst)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::copy(src, dst).map(|_| ())
+}
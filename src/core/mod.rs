@@ -1,3 +1,17 @@
+pub mod activation;
 pub mod api;
+pub mod archives;
+pub mod bundle;
+pub mod completions;
+pub mod config;
+pub mod discover;
+pub mod doctor;
 pub mod env;
-pub mod install;
\ No newline at end of file
+pub mod exec;
+pub mod hooks;
+pub mod init;
+pub mod install;
+pub mod prune;
+pub mod shims;
+pub mod version;
+pub mod which;
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use crate::core::env;
+use crate::core::version::VersionId;
+
+/// Rules deciding which installed versions `sdk prune` may remove.
+///
+/// A version is removed only if it passes every configured rule; the
+/// current version and pinned versions are always kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrunePolicy {
+    /// Number of newest versions to keep per candidate.
+    pub keep: Option<usize>,
+    /// Only remove versions installed longer ago than this.
+    pub older_than: Option<Duration>,
+}
+
+/// An installed version considered for pruning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledVersion {
+    pub version: String,
+    pub installed_at: Option<SystemTime>,
+}
+
+/// Selects the versions of a candidate that the policy allows to remove.
+///
+/// # Arguments
+/// * `installed` - Installed versions of one candidate
+/// * `protected` - Versions that must never be removed (current, pinned)
+/// * `policy` - Keep/age rules
+/// * `now` - Reference time for `older_than`
+///
+/// # Returns
+/// Version identifiers to remove, newest first
+pub fn select_prunable(installed: &[InstalledVersion], protected: &HashSet<String>, policy: &PrunePolicy, now: SystemTime) -> Vec<String> {
+    let mut sorted: Vec<&InstalledVersion> = installed.iter().collect();
+    sorted.sort_by(|a, b| VersionId::parse(&b.version).cmp_version(&VersionId::parse(&a.version)));
+
+    sorted
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| policy.keep.is_none_or(|keep| *i >= keep))
+        .map(|(_, v)| v)
+        .filter(|v| !protected.contains(&v.version))
+        .filter(|v| match policy.older_than {
+            Some(age) => v.installed_at
+                .and_then(|t| now.duration_since(t).ok())
+                .is_some_and(|elapsed| elapsed > age),
+            None => true,
+        })
+        .map(|v| v.version.clone())
+        .collect()
+}
+
+/// Collects the versions of a candidate that must survive pruning.
+///
+/// Includes the global and local `current` versions plus every version
+/// pinned for the candidate in the given `.sdkmanrc` files. Unreadable
+/// `.sdkmanrc` files are skipped.
+pub fn protected_versions(candidate: &str, sdkmanrc_files: &[&Path]) -> HashSet<String> {
+    let mut protected: HashSet<String> = [
+        env::get_current_version(candidate),
+        env::get_local_current_version(candidate),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    for rc in sdkmanrc_files {
+        if let Ok(pins) = env::read_sdkmanrc(rc) {
+            if let Some(version) = pins.get(candidate) {
+                protected.insert(version.clone());
+            }
+        }
+    }
+
+    protected
+}
@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use crate::constants;
+use crate::core::{env, exec, which};
+
+/// Command-line flag that makes the plugin binary act as a shim:
+/// `nu_plugin_sdkman --shim <candidate> <binary> [args...]`.
+pub const SHIM_FLAG: &str = "--shim";
+
+/// First line after the interpreter line of every generated shim.
+const SHIM_MARKER: &str = "SDKMAN shim, generated by `sdk shims rehash`";
+
+/// Returns the shim directory (`~/.sdkman/shims`).
+pub fn shims_dir() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(env::sdkman_dir()?.join(constants::SHIMS_DIR))
+}
+
+/// A generated shim and the candidate whose binary it runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shim {
+    pub name: String,
+    pub candidate: String,
+    /// Other candidates providing a binary of the same name; the shim
+    /// never runs theirs.
+    pub shadowed: Vec<String>,
+    pub path: PathBuf,
+}
+
+/// Maps every binary of every installed version to the candidates
+/// providing it, in name order.
+///
+/// A binary name provided by several candidates is shimmed for the first.
+/// Only `~/.sdkman` installs are scanned: a binary that exists only in a
+/// project's isolated `.sdkman` gets no shim (while [`resolve`] still
+/// prefers isolated installs for binaries that have one).
+pub fn shim_targets() -> BTreeMap<String, Vec<String>> {
+    let mut targets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for candidate in env::get_installed_candidates() {
+        for version in env::get_installed_versions(&candidate) {
+            let Ok(bin) = env::candidate_dir(&candidate, &version).map(|d| d.join("bin")) else { continue };
+            let Ok(entries) = std::fs::read_dir(bin) else { continue };
+            for entry in entries.filter_map(|e| e.ok()).filter(|e| e.path().is_file()) {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let name = shim_name(&name);
+                let candidates = targets.entry(name).or_default();
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate.clone());
+                }
+            }
+        }
+    }
+    targets
+}
+
+/// Regenerates the shims: one per installed binary, stale ones removed.
+///
+/// Shims run `plugin_binary` in shim mode, which picks the version at run
+/// time (see [`resolve`]).
+///
+/// # Returns
+/// The shims now in place
+///
+/// # Errors
+/// Returns error if the shim directory cannot be written
+pub fn rehash(plugin_binary: &Path) -> Result<Vec<Shim>, Box<dyn Error>> {
+    let dir = shims_dir()?;
+    std::fs::create_dir_all(&dir)?;
+
+    let targets = shim_targets();
+    for entry in std::fs::read_dir(&dir)?.filter_map(|e| e.ok()) {
+        let generated = std::fs::read_to_string(entry.path()).is_ok_and(|c| c.contains(SHIM_MARKER));
+        let name = shim_name(&entry.file_name().to_string_lossy());
+        if generated && !targets.contains_key(&name) {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+
+    for (binary, candidates) in &targets {
+        write_shim(&dir, plugin_binary, &candidates[0], binary)?;
+    }
+    list()
+}
+
+/// Lists the shims present in the shim directory.
+pub fn list() -> Result<Vec<Shim>, Box<dyn Error>> {
+    let dir = shims_dir()?;
+    Ok(shim_targets()
+        .into_iter()
+        .map(|(name, mut candidates)| {
+            let candidate = candidates.remove(0);
+            Shim { path: shim_path(&dir, &name), name, candidate, shadowed: candidates }
+        })
+        .filter(|shim| shim.path.is_file())
+        .collect())
+}
+
+fn shim_path(dir: &Path, binary: &str) -> PathBuf {
+    if cfg!(windows) { dir.join(format!("{}.cmd", binary)) } else { dir.join(binary) }
+}
+
+/// Rehashes if shims are in use, so newly installed binaries get a shim.
+pub fn refresh() {
+    let in_use = shims_dir().is_ok_and(|dir| dir.is_dir());
+    if in_use {
+        if let Ok(plugin_binary) = std::env::current_exe() {
+            rehash(&plugin_binary).ok();
+        }
+    }
+}
+
+/// Works out which version of a candidate a shim runs in `cwd`: the pin of
+/// the nearest `.sdkmanrc`, else the global current version.
+///
+/// # Returns
+/// The version and its installation directory (preferring the isolated
+/// installation of the `.sdkmanrc`'s project)
+///
+/// # Errors
+/// Returns error if no version is pinned or current, or it isn't installed
+pub fn resolve(candidate: &str, cwd: &Path) -> Result<(String, PathBuf), Box<dyn Error>> {
+    let rc = env::find_sdkmanrc(cwd);
+    let pinned = rc
+        .as_ref()
+        .and_then(|rc| env::read_sdkmanrc(rc).ok())
+        .and_then(|pins| pins.get(candidate).cloned());
+
+    let (version, project_dir) = match pinned {
+        Some(version) => (version, rc.as_ref().and_then(|rc| rc.parent()).map(Path::to_path_buf)),
+        None => (
+            env::get_current_version(candidate)
+                .ok_or_else(|| format!("No version of {} is pinned in .sdkmanrc or set as default", candidate))?,
+            None,
+        ),
+    };
+
+    let isolated = project_dir
+        .map(|dir| env::local_candidate_dir(&dir.join(constants::SDKMAN_DIR_NAME), candidate, &version))
+        .filter(|dir| dir.exists());
+    let home = match isolated {
+        Some(dir) => dir,
+        None => env::candidate_dir(candidate, &version)?,
+    };
+    if !home.exists() {
+        return Err(format!("{} {} is not installed (run: sdk install {} {})", candidate, version, candidate, version).into());
+    }
+    Ok((version, home))
+}
+
+/// Returns true if `path` is a shim generated in the shim directory.
+pub fn is_shim(path: &Path) -> bool {
+    let (Ok(dir), Some(parent)) = (shims_dir(), path.parent()) else { return false };
+    let in_dir = parent == dir || matches!((parent.canonicalize(), dir.canonicalize()), (Ok(a), Ok(b)) if a == b);
+    in_dir && std::fs::read_to_string(path).is_ok_and(|c| c.contains(SHIM_MARKER))
+}
+
+/// Returns the candidate the shim at `path` runs, looked up in `targets`
+/// (from [`shim_targets`]).
+pub fn shim_candidate(path: &Path, targets: &BTreeMap<String, Vec<String>>) -> Option<String> {
+    let name = shim_name(&path.file_name()?.to_string_lossy());
+    targets.get(&name).and_then(|candidates| candidates.first().cloned())
+}
+
+/// Entry point of shim mode: resolves the version for the working directory
+/// and runs its `binary` with `args` and the environment of `sdk exec`: the
+/// candidate's version plus everything pinned in the nearest `.sdkmanrc`.
+///
+/// # Returns
+/// The exit code to exit with
+pub fn run_shim(candidate: &str, binary: &str, args: &[String]) -> i32 {
+    match exec_shim(candidate, binary, args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("sdkman shim {}: {}", binary, e);
+            127
+        }
+    }
+}
+
+fn exec_shim(candidate: &str, binary: &str, args: &[String]) -> Result<i32, Box<dyn Error>> {
+    let cwd = std::env::current_dir()?;
+    let (version, home) = resolve(candidate, &cwd)?;
+    let program = which::executable_in(&home.join("bin"), binary)
+        .ok_or_else(|| format!("{} {} has no {} binary", candidate, version, binary))?;
+
+    let toolchain = exec::resolve_toolchain(&[format!("{}@{}", candidate, version)], &cwd)?;
+    let vars = exec::command_env(&toolchain, &std::env::vars().collect())?;
+
+    let mut command = std::process::Command::new(&program);
+    command.args(args).env_clear().envs(&vars);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Only returns if the program could not be started
+        Err(command.exec().into())
+    }
+
+    #[cfg(not(unix))]
+    {
+        Ok(command.status()?.code().unwrap_or(1))
+    }
+}
+
+/// Shim name of a binary file (Windows extensions stripped).
+fn shim_name(file_name: &str) -> String {
+    if cfg!(windows) {
+        for ext in [".exe", ".cmd", ".bat"] {
+            if let Some(stem) = file_name.strip_suffix(ext) {
+                return stem.to_string();
+            }
+        }
+    }
+    file_name.to_string()
+}
+
+#[cfg(unix)]
+fn write_shim(dir: &Path, plugin_binary: &Path, candidate: &str, binary: &str) -> Result<(), Box<dyn Error>> {
+    use std::os::unix::fs::PermissionsExt;
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+    let script = format!(
+        "#!/bin/sh\n# {}\nexec {} {} {} {} \"$@\"\n",
+        SHIM_MARKER,
+        quote(&plugin_binary.display().to_string()),
+        SHIM_FLAG,
+        quote(candidate),
+        quote(binary),
+    );
+    let path = shim_path(dir, binary);
+    std::fs::write(&path, script)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn write_shim(dir: &Path, plugin_binary: &Path, candidate: &str, binary: &str) -> Result<(), Box<dyn Error>> {
+    let script = format!(
+        "@echo off\r\nrem {}\r\n\"{}\" {} \"{}\" \"{}\" %*\r\nexit /b %ERRORLEVEL%\r\n",
+        SHIM_MARKER,
+        plugin_binary.display(),
+        SHIM_FLAG,
+        candidate,
+        binary,
+    );
+    std::fs::write(shim_path(dir, binary), script)?;
+    Ok(())
+}
@@ -0,0 +1,235 @@
+use std::cmp::Ordering;
+use crate::core::api::VersionInfo;
+
+/// Java feature releases designated as LTS before the two-year cadence started.
+const JAVA_EARLY_LTS: &[u64] = &[8, 11];
+/// First Java release of the two-year LTS cadence (17, 21, 25, ...).
+const JAVA_LTS_CADENCE_START: u64 = 17;
+const JAVA_LTS_CADENCE: u64 = 4;
+
+/// Qualifier fragments marking early-access or pre-release builds.
+const PRERELEASE_MARKERS: &[&str] = &["ea", "rc", "alpha", "beta", "snapshot", "milestone"];
+
+/// A parsed SDKMAN version identifier.
+///
+/// Identifiers have the shape `<numbers>[.<qualifier>][-<vendor>]`, e.g.
+/// `17.0.9-tem`, `23.1.9.r21-mandrel`, `8.5` or `26.ea.13-open`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionId {
+    pub raw: String,
+    pub numbers: Vec<u64>,
+    pub qualifier: String,
+    pub vendor: String,
+}
+
+impl VersionId {
+    /// Parses a version identifier.
+    ///
+    /// The vendor is everything after the first hyphen (matching
+    /// `api::parse_versions_text`). Leading dot-separated numeric components
+    /// become `numbers`; anything after the first non-numeric component is
+    /// kept as `qualifier`.
+    pub fn parse(identifier: &str) -> Self {
+        let identifier = identifier.trim();
+        let (version, vendor) = identifier.split_once('-').unwrap_or((identifier, ""));
+
+        let mut numbers = Vec::new();
+        let mut rest = Vec::new();
+        for part in version.split('.') {
+            match part.parse::<u64>() {
+                Ok(n) if rest.is_empty() => numbers.push(n),
+                _ => rest.push(part),
+            }
+        }
+
+        VersionId {
+            raw: identifier.to_string(),
+            numbers,
+            qualifier: rest.join("."),
+            vendor: vendor.to_string(),
+        }
+    }
+
+    /// Major version component (0 if the identifier has no numeric part).
+    pub fn major(&self) -> u64 {
+        self.numbers.first().copied().unwrap_or(0)
+    }
+
+    /// Minor version component (0 if absent).
+    pub fn minor(&self) -> u64 {
+        self.numbers.get(1).copied().unwrap_or(0)
+    }
+
+    /// Returns true for early-access, release-candidate and similar builds.
+    pub fn is_prerelease(&self) -> bool {
+        let qualifier = self.qualifier.to_lowercase();
+        let vendor = self.vendor.to_lowercase();
+        PRERELEASE_MARKERS.iter().any(|m| {
+            qualifier.split('.').any(|p| p.starts_with(m))
+                || vendor.split('-').any(|p| p.starts_with(m))
+        })
+    }
+
+    /// Compares the version part of two identifiers, ignoring the vendor.
+    ///
+    /// Numeric components are compared first (missing components count as 0),
+    /// then releases sort above pre-releases, then qualifiers lexically.
+    pub fn cmp_version(&self, other: &Self) -> Ordering {
+        let len = self.numbers.len().max(other.numbers.len());
+        for i in 0..len {
+            let a = self.numbers.get(i).copied().unwrap_or(0);
+            let b = other.numbers.get(i).copied().unwrap_or(0);
+            match a.cmp(&b) {
+                Ordering::Equal => continue,
+                ord => return ord,
+            }
+        }
+
+        other.is_prerelease().cmp(&self.is_prerelease())
+            .then_with(|| self.qualifier.cmp(&other.qualifier))
+    }
+}
+
+/// Returns true if the version belongs to a long-term-support line.
+///
+/// Only Java publishes an LTS schedule; for other candidates every version
+/// is considered eligible so `--lts` never filters them out.
+pub fn is_lts(candidate: &str, version: &VersionId) -> bool {
+    if candidate != "java" {
+        return true;
+    }
+    let major = version.major();
+    JAVA_EARLY_LTS.contains(&major)
+        || (major >= JAVA_LTS_CADENCE_START && (major - JAVA_LTS_CADENCE_START).is_multiple_of(JAVA_LTS_CADENCE))
+}
+
+/// How far an upgrade may move away from the current version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Within {
+    /// Stay on the same major and minor version.
+    Minor,
+    /// Stay on the same major version.
+    Major,
+    /// Move to any newer version.
+    Any,
+}
+
+impl Within {
+    /// Scope used when none is given: JDK majors are separate release
+    /// lines (17, 21, ...), so java stays within its major; other
+    /// candidates upgrade to any newer version as bash SDKMAN does.
+    pub fn default_for(candidate: &str) -> Self {
+        if candidate == "java" { Within::Major } else { Within::Any }
+    }
+}
+
+impl std::str::FromStr for Within {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minor" => Ok(Within::Minor),
+            "major" => Ok(Within::Major),
+            "any" => Ok(Within::Any),
+            other => Err(format!("Unknown upgrade scope: {}. Use 'minor', 'major' or 'any'", other)),
+        }
+    }
+}
+
+/// Rules used to choose an upgrade target for an installed version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpgradePolicy {
+    /// Upgrade scope; `None` uses [`Within::default_for`] the candidate.
+    pub within: Option<Within>,
+    pub same_vendor: bool,
+    pub lts_only: bool,
+}
+
+impl Default for UpgradePolicy {
+    fn default() -> Self {
+        UpgradePolicy {
+            within: None,
+            same_vendor: true,
+            lts_only: false,
+        }
+    }
+}
+
+impl UpgradePolicy {
+    /// Checks whether `target` is an acceptable upgrade of `current`.
+    fn allows(&self, candidate: &str, current: &VersionId, target: &VersionId) -> bool {
+        if self.same_vendor && target.vendor != current.vendor {
+            return false;
+        }
+        if self.lts_only && !is_lts(candidate, target) {
+            return false;
+        }
+        if target.is_prerelease() && !current.is_prerelease() {
+            return false;
+        }
+        match self.within.unwrap_or_else(|| Within::default_for(candidate)) {
+            Within::Minor => target.major() == current.major() && target.minor() == current.minor(),
+            Within::Major => target.major() == current.major(),
+            Within::Any => true,
+        }
+    }
+}
+
+/// Picks the newest available version that satisfies the upgrade policy.
+///
+/// # Arguments
+/// * `candidate` - Candidate name (e.g., "java")
+/// * `current` - Currently active version identifier
+/// * `available` - Versions offered by the API
+/// * `policy` - Upgrade constraints
+///
+/// # Returns
+/// The identifier to upgrade to, or None if nothing newer matches the policy
+pub fn select_upgrade(candidate: &str, current: &str, available: &[VersionInfo], policy: &UpgradePolicy) -> Option<String> {
+    let current = VersionId::parse(current);
+    available
+        .iter()
+        .map(|v| VersionId::parse(&v.version))
+        .filter(|v| policy.allows(candidate, &current, v))
+        .filter(|v| v.cmp_version(&current) == Ordering::Greater)
+        .max_by(|a, b| a.cmp_version(b))
+        .map(|v| v.raw)
+}
+
+/// Returns the newest non-prerelease version from a list.
+///
+/// # Arguments
+/// * `available` - Versions offered by the API
+/// * `vendor` - Restrict the search to this vendor, if given
+pub fn latest_release(available: &[VersionInfo], vendor: Option<&str>) -> Option<String> {
+    available
+        .iter()
+        .map(|v| VersionId::parse(&v.version))
+        .filter(|v| !v.is_prerelease())
+        .filter(|v| vendor.is_none_or(|vendor| v.vendor == vendor))
+        .max_by(|a, b| a.cmp_version(b))
+        .map(|v| v.raw)
+}
+
+/// Picks the version a version spec refers to.
+///
+/// An exact identifier wins. Otherwise the spec's numbers must be a prefix
+/// of the version's (`17` matches `17.0.9-tem`) and its qualifier and
+/// vendor, if given, must be equal (`17-tem`); the newest match is returned.
+pub fn match_version(spec: &str, versions: &[String]) -> Option<String> {
+    if let Some(exact) = versions.iter().find(|v| *v == spec) {
+        return Some(exact.clone());
+    }
+    let spec = VersionId::parse(spec);
+    if spec.numbers.is_empty() {
+        return None;
+    }
+    versions
+        .iter()
+        .map(|v| VersionId::parse(v))
+        .filter(|v| v.numbers.starts_with(&spec.numbers))
+        .filter(|v| spec.qualifier.is_empty() || v.qualifier == spec.qualifier)
+        .filter(|v| spec.vendor.is_empty() || v.vendor == spec.vendor)
+        .max_by(|a, b| a.cmp_version(b))
+        .map(|v| v.raw)
+}
@@ -0,0 +1,179 @@
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::constants;
+use crate::core::{env, shims};
+
+/// Where a binary found on PATH comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinarySource {
+    /// The project's `.sdkman/candidates`.
+    Local,
+    /// `~/.sdkman/candidates`.
+    Global,
+    /// A shim in `~/.sdkman/shims`; candidate and version are those it
+    /// runs in the project.
+    Shim,
+    /// Anything not managed by SDKMAN.
+    System,
+}
+
+impl fmt::Display for BinarySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinarySource::Local => write!(f, "local"),
+            BinarySource::Global => write!(f, "global"),
+            BinarySource::Shim => write!(f, "shim"),
+            BinarySource::System => write!(f, "system"),
+        }
+    }
+}
+
+/// A binary found by [`which`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryLocation {
+    pub binary: String,
+    pub path: PathBuf,
+    /// Owning candidate, `None` for system binaries.
+    pub candidate: Option<String>,
+    /// Owning version (resolved through `current` links).
+    pub version: Option<String>,
+    pub source: BinarySource,
+}
+
+/// Finds every copy of `binary`, the one that runs first.
+///
+/// Directories of `path_var` are searched in order; then `current/bin` of
+/// every candidate in the project's `.sdkman` and in `~/.sdkman` (in that
+/// order) that is not on PATH, so shadowed SDKMAN binaries are reported too.
+///
+/// # Arguments
+/// * `binary` - Executable name (e.g. `java`)
+/// * `path_var` - The effective PATH
+/// * `project_dir` - Directory whose `.sdkman` counts as the local environment
+pub fn which(binary: &str, path_var: Option<&str>, project_dir: &Path) -> Vec<BinaryLocation> {
+    let local_dir = project_dir.join(constants::SDKMAN_DIR_NAME);
+    let local_candidates = local_dir.join(constants::CANDIDATES_DIR);
+    let global_candidates = env::candidates_dir().ok();
+
+    let mut dirs: Vec<PathBuf> = path_var.map(|p| std::env::split_paths(p).collect()).unwrap_or_default();
+    for candidates_dir in std::iter::once(&local_candidates).chain(global_candidates.as_ref()) {
+        for candidate in read_dir_names(candidates_dir) {
+            dirs.push(candidates_dir.join(candidate).join(constants::CURRENT_LINK).join("bin"));
+        }
+    }
+
+    // Scanning every bin directory is only worth it once a shim is found
+    let shim_targets = OnceCell::new();
+    let mut found: Vec<BinaryLocation> = Vec::new();
+    for dir in dirs {
+        let Some(path) = executable_in(&dir, binary) else { continue };
+        if found.iter().any(|f| same_file(&f.path, &path)) {
+            continue;
+        }
+        let mut location = classify(&path, &local_candidates, global_candidates.as_deref(), project_dir, &shim_targets);
+        location.binary = binary.to_string();
+        found.push(location);
+    }
+    found
+}
+
+/// Returns the executable named `program` in `dir`, trying the Windows
+/// executable extensions when on Windows.
+///
+/// On unix, files without an execute bit are skipped, as the shell does.
+pub fn executable_in(dir: &Path, program: &str) -> Option<PathBuf> {
+    let suffixes: &[&str] = if cfg!(windows) { &["", ".exe", ".cmd", ".bat"] } else { &[""] };
+    suffixes
+        .iter()
+        .map(|suffix| dir.join(format!("{}{}", program, suffix)))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Works out which candidate and version a binary belongs to from its
+/// location under a `candidates` directory; shims report the version they
+/// run in `project_dir`.
+fn classify(
+    path: &Path,
+    local_candidates: &Path,
+    global_candidates: Option<&Path>,
+    project_dir: &Path,
+    shim_targets: &OnceCell<BTreeMap<String, Vec<String>>>,
+) -> BinaryLocation {
+    let shim = shims::is_shim(path)
+        .then(|| shims::shim_candidate(path, shim_targets.get_or_init(shims::shim_targets)))
+        .flatten();
+    if let Some(candidate) = shim {
+        let version = shims::resolve(&candidate, project_dir).ok().map(|(version, _)| version);
+        return BinaryLocation {
+            binary: String::new(),
+            path: path.to_path_buf(),
+            candidate: Some(candidate),
+            version,
+            source: BinarySource::Shim,
+        };
+    }
+
+    let roots = [(BinarySource::Local, Some(local_candidates)), (BinarySource::Global, global_candidates)];
+    for (source, root) in roots {
+        let Some(root) = root else { continue };
+        let Some(relative) = strip_root(path, root) else { continue };
+        let mut parts = relative.components().map(|c| c.as_os_str().to_string_lossy().into_owned());
+        let (Some(candidate), Some(version)) = (parts.next(), parts.next()) else { continue };
+
+        let version = if version == constants::CURRENT_LINK {
+            env::read_current(&root.join(&candidate).join(constants::CURRENT_LINK))
+        } else {
+            Some(version)
+        };
+        return BinaryLocation {
+            binary: String::new(),
+            path: path.to_path_buf(),
+            candidate: Some(candidate),
+            version,
+            source,
+        };
+    }
+    BinaryLocation { binary: String::new(), path: path.to_path_buf(), candidate: None, version: None, source: BinarySource::System }
+}
+
+/// Strips `root` from `path`, comparing canonical forms when the literal
+/// paths differ (e.g. a symlinked home directory).
+fn strip_root(path: &Path, root: &Path) -> Option<PathBuf> {
+    if let Ok(relative) = path.strip_prefix(root) {
+        return Some(relative.to_path_buf());
+    }
+    let root = root.canonicalize().ok()?;
+    let parent = path.parent()?.canonicalize().ok()?;
+    Some(parent.strip_prefix(&root).ok()?.join(path.file_name()?))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+fn read_dir_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
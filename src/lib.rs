@@ -5,7 +5,7 @@ pub mod utils;
 
 use nu_plugin::{Plugin, PluginCommand};
 use commands::{Sdk, List, Install, Uninstall, Use, Current, Upgrade, Default, Home, Version};
-use commands::{Env, Update, Flush, Config, Offline};
+use commands::{Env, Update, Flush, Du, Doctor, Config, Offline, Outdated, Prune, Bundle, Cache, Link, Discover, Init, Exec, Which, Shims};
 use commands::{I, Rm, Ls, U, C, Ug, D, H, V};
 
 pub struct SdkmanPlugin;
@@ -31,8 +31,20 @@ impl Plugin for SdkmanPlugin {
             Box::new(Env),
             Box::new(Update),
             Box::new(Flush),
+            Box::new(Du),
+            Box::new(Doctor),
             Box::new(Config),
             Box::new(Offline),
+            Box::new(Outdated),
+            Box::new(Prune),
+            Box::new(Bundle),
+            Box::new(Cache),
+            Box::new(Link),
+            Box::new(Discover),
+            Box::new(Init),
+            Box::new(Exec),
+            Box::new(Which),
+            Box::new(Shims),
             // Aliases
             Box::new(Ls),
             Box::new(I),
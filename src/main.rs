@@ -1,6 +1,12 @@
 use nu_plugin::{serve_plugin, MsgPackSerializer};
+use nu_plugin_sdkman::core::shims;
 use nu_plugin_sdkman::SdkmanPlugin;
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    // Shims generated by `sdk shims rehash` run `<plugin> --shim <candidate> <binary> [args...]`
+    if args.get(1).map(String::as_str) == Some(shims::SHIM_FLAG) && args.len() >= 4 {
+        std::process::exit(shims::run_shim(&args[2], &args[3], &args[4..]));
+    }
     serve_plugin(&SdkmanPlugin, MsgPackSerializer);
 }
@@ -1,8 +1,11 @@
 use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use std::error::Error;
 use std::fs::File;
+use std::io::Read;
 use std::path::Path;
-use tar::Archive;
+use tar::{Archive, Builder};
 use zip::ZipArchive;
 
 /// Extracts a tar.gz archive to the destination directory.
@@ -27,6 +30,32 @@ pub fn extract_tar_gz(archive: &Path, destination: &Path) -> Result<(), Box<dyn
     Ok(())
 }
 
+/// Reads a single file from a tar.gz archive without extracting the rest.
+///
+/// Stops reading the archive as soon as the file is found.
+///
+/// # Arguments
+/// * `archive` - Path to the .tar.gz file
+/// * `name` - Path of the file inside the archive
+///
+/// # Errors
+/// Returns error if the archive cannot be read; `Ok(None)` if the file is missing
+pub fn read_tar_gz_file(archive: &Path, name: &Path) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    let file = File::open(archive)?;
+    let mut archive = Archive::new(GzDecoder::new(file));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.components().eq(name.components()) {
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            return Ok(Some(content));
+        }
+    }
+
+    Ok(None)
+}
+
 /// Extracts a zip archive to the destination directory.
 ///
 /// Uses pure Rust implementation (no external unzip command required).
@@ -64,13 +93,41 @@ pub fn extract_zip(archive: &Path, destination: &Path) -> Result<(), Box<dyn Err
     Ok(())
 }
 
-/// Extracts an archive based on file extension.
+/// Archive formats understood by [`extract`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+/// Detects the archive format from the file's magic bytes, falling back to
+/// its extension.
 ///
-/// Automatically detects format and calls appropriate extraction function.
+/// Downloads and user-supplied files often have misleading or missing
+/// extensions (e.g. a zip served for a `.tar.gz` request), so the contents
+/// take precedence.
+pub fn detect_format(archive: &Path) -> Option<ArchiveFormat> {
+    let mut magic = [0u8; 4];
+    let read = File::open(archive).and_then(|mut f| f.read(&mut magic)).unwrap_or(0);
+    
+    match &magic[..read] {
+        [0x1f, 0x8b, ..] => return Some(ArchiveFormat::TarGz),
+        [b'P', b'K', 0x03, 0x04] | [b'P', b'K', 0x05, 0x06] => return Some(ArchiveFormat::Zip),
+        _ => {}
+    }
+    
+    match archive.extension().and_then(|s| s.to_str()) {
+        Some("gz") | Some("tgz") => Some(ArchiveFormat::TarGz),
+        Some("zip") => Some(ArchiveFormat::Zip),
+        _ => None,
+    }
+}
+
+/// Extracts an archive, detecting its format.
 ///
 /// # Supported Formats
-/// - `.gz` - tar.gz archives (Unix/Linux standard)
-/// - `.zip` - zip archives (Windows standard)
+/// - tar.gz archives (Unix/Linux standard)
+/// - zip archives (Windows standard)
 ///
 /// # Arguments
 /// * `archive` - Path to the archive file
@@ -79,11 +136,38 @@ pub fn extract_zip(archive: &Path, destination: &Path) -> Result<(), Box<dyn Err
 /// # Errors
 /// Returns error if format is unsupported or extraction fails
 pub fn extract(archive: &Path, destination: &Path) -> Result<(), Box<dyn Error>> {
-    let ext = archive.extension().and_then(|s| s.to_str()).unwrap_or("");
+    match detect_format(archive) {
+        Some(ArchiveFormat::TarGz) => extract_tar_gz(archive, destination),
+        Some(ArchiveFormat::Zip) => extract_zip(archive, destination),
+        None => Err(format!("Unsupported archive format: {}", archive.display()).into()),
+    }
+}
+
+/// Creates a tar.gz archive from files and directory trees.
+///
+/// Symlinks are stored as links rather than followed, so installations that
+/// rely on relative links (e.g. `bin/java -> ../lib/...`) survive a round trip.
+///
+/// # Arguments
+/// * `archive` - Path of the .tar.gz file to create
+/// * `entries` - Source paths paired with their path inside the archive
+///
+/// # Errors
+/// Returns error if a source cannot be read or the archive cannot be written
+pub fn create_tar_gz(archive: &Path, entries: &[(&Path, &Path)]) -> Result<(), Box<dyn Error>> {
+    let file = File::create(archive)?;
+    let mut builder = Builder::new(GzEncoder::new(file, Compression::default()));
+    builder.follow_symlinks(false);
     
-    match ext {
-        "gz" => extract_tar_gz(archive, destination),
-        "zip" => extract_zip(archive, destination),
-        _ => Err("Unsupported archive format".into()),
+    for (source, name) in entries {
+        if source.is_dir() {
+            builder.append_dir_all(name, source)?;
+        } else {
+            builder.append_path_with_name(source, name)?;
+        }
     }
+    
+    builder.into_inner()?.finish()?;
+    
+    Ok(())
 }
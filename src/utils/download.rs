@@ -1,6 +1,41 @@
 use std::error::Error;
 use std::fs::File;
 use std::path::Path;
+use std::time::Duration;
+use reqwest::blocking::Client;
+use crate::core::config::Config;
+
+/// Builds an HTTP client for SDKMAN API requests.
+///
+/// Honors `sdkman_curl_connect_timeout`, `sdkman_curl_max_time` and
+/// `sdkman_insecure_ssl` from `~/.sdkman/etc/config`.
+///
+/// # Errors
+/// Returns error if the TLS backend cannot be initialized
+pub fn api_client() -> Result<Client, Box<dyn Error>> {
+    let config = Config::load();
+    Ok(client_builder(&config)
+        .timeout(Duration::from_secs(config.curl_max_time))
+        .build()?)
+}
+
+/// Builds an HTTP client for archive downloads.
+///
+/// Same as [`api_client`] but without a total timeout, since SDK archives
+/// can take minutes to download.
+///
+/// # Errors
+/// Returns error if the TLS backend cannot be initialized
+pub fn download_client() -> Result<Client, Box<dyn Error>> {
+    let config = Config::load();
+    Ok(client_builder(&config).timeout(None).build()?)
+}
+
+fn client_builder(config: &Config) -> reqwest::blocking::ClientBuilder {
+    Client::builder()
+        .connect_timeout(Duration::from_secs(config.curl_connect_timeout))
+        .danger_accept_invalid_certs(config.insecure_ssl)
+}
 
 /// Downloads a file from a URL to the specified output path.
 ///
@@ -17,7 +52,7 @@ use std::path::Path;
 /// - File cannot be created
 /// - Write operation fails
 pub fn download_file(url: &str, output: &Path) -> Result<(), Box<dyn Error>> {
-    let response = reqwest::blocking::get(url)?;
+    let response = download_client()?.get(url).send()?.error_for_status()?;
     let mut file = File::create(output)?;
     let content = response.bytes()?;
     std::io::copy(&mut content.as_ref(), &mut file)?;
@@ -0,0 +1,66 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Disk usage of a directory tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirSize {
+    pub bytes: u64,
+    pub files: u64,
+}
+
+/// Recursively computes the size of a directory.
+///
+/// Symbolic links are counted as entries but never followed, so linked
+/// versions and `current` links do not count the target's size twice.
+///
+/// # Arguments
+/// * `path` - Directory (or file) to measure
+///
+/// # Errors
+/// Returns error if the top-level path cannot be read
+pub fn dir_size(path: &Path) -> std::io::Result<DirSize> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(DirSize { bytes: metadata.len(), files: 1 });
+    }
+    
+    let mut total = DirSize::default();
+    for entry in std::fs::read_dir(path)?.filter_map(|e| e.ok()) {
+        // Unreadable subtrees are skipped rather than failing the whole walk
+        if let Ok(size) = dir_size(&entry.path()) {
+            total.bytes += size.bytes;
+            total.files += size.files;
+        }
+    }
+    Ok(total)
+}
+
+/// Returns true if `name` is exactly one plain path component.
+///
+/// Used to check names that are joined onto install paths, e.g. version
+/// names, so they cannot point outside their parent directory.
+pub fn is_plain_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!((components.next(), components.next()), (Some(Component::Normal(_)), None))
+}
+
+/// Computes the path of `to` relative to the directory `from_dir`.
+///
+/// Both paths should be absolute and free of `..` components (e.g.
+/// canonicalized); the result is purely lexical.
+///
+/// # Example
+/// `relative_path("/a/b/c", "/a/d")` is `../../d`
+pub fn relative_path(from_dir: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component> = from_dir.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..from.len() {
+        relative.push("..");
+    }
+    for component in &to[common..] {
+        relative.push(component);
+    }
+    relative
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::activation::{self, Activation, Shell, DEACTIVATE_FN};
+
+    fn activation() -> Activation {
+        Activation {
+            path_entries: vec![PathBuf::from("/p/.sdkman/candidates/java/current/bin")],
+            vars: vec![("JAVA_HOME".into(), "/p/.sdkman/candidates/java/current".into())],
+        }
+    }
+
+    #[test]
+    fn test_every_shell_sets_path_vars_and_deactivate() {
+        let activation = activation();
+        for shell in Shell::ALL {
+            let script = activation.render(shell);
+            assert!(script.contains("/p/.sdkman/candidates/java/current/bin"), "{:?}", shell);
+            assert!(script.contains("JAVA_HOME"), "{:?}", shell);
+            assert!(script.contains(DEACTIVATE_FN), "{:?}", shell);
+        }
+    }
+
+    #[test]
+    fn test_elvish_exports_deactivate_to_repl() {
+        let script = activation().render(Shell::Elvish);
+        assert!(script.contains(&format!("edit:add-var {0}~ ${0}~", DEACTIVATE_FN)));
+        assert_eq!(Shell::Elvish.activate_command(".sdkman/env.elv"), "eval (slurp < .sdkman/env.elv)");
+    }
+
+    #[test]
+    fn test_quoting() {
+        let activation = Activation {
+            path_entries: vec![PathBuf::from("/it's/bin")],
+            vars: Vec::new(),
+        };
+        assert!(activation.render(Shell::Sh).contains(r#"PATH='/it'\''s/bin':"$PATH""#));
+        assert!(activation.render(Shell::Fish).contains(r"'/it\'s/bin'"));
+        assert!(activation.render(Shell::PowerShell).contains("'/it''s/bin'"));
+        assert!(activation.render(Shell::Elvish).contains("'/it''s/bin'"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sh_activate_and_deactivate_restore_environment() {
+        let temp = tempdir().unwrap();
+        let script = temp.path().join("env.sh");
+        fs::write(&script, activation().render(Shell::Sh)).unwrap();
+
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!(
+                ". {0}; echo \"$PATH|$JAVA_HOME\"; . {0}; {1}; echo \"$PATH|${{JAVA_HOME-unset}}\"",
+                script.display(),
+                DEACTIVATE_FN
+            ))
+            .env("PATH", "/usr/bin:/bin")
+            .env_remove("JAVA_HOME")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+
+        assert_eq!(lines[0], "/p/.sdkman/candidates/java/current/bin:/usr/bin:/bin|/p/.sdkman/candidates/java/current");
+        // Activating twice doesn't stack PATH entries, and deactivate restores everything
+        assert_eq!(lines[1], "/usr/bin:/bin|unset");
+    }
+
+    #[test]
+    #[serial]
+    fn test_write_local_scripts_uses_pins_and_links() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path().join("global"));
+        let project = temp.path().join("project");
+        fs::create_dir_all(project.join(".sdkman/candidates/maven")).unwrap();
+        fs::write(project.join(".sdkmanrc"), "java=17.0.0\n").unwrap();
+
+        assert_eq!(activation::local_candidates(&project), vec!["java", "maven"]);
+
+        let written = activation::write_local_scripts(&project).unwrap();
+        assert_eq!(written.len(), Shell::ALL.len());
+        let script = fs::read_to_string(project.join(".sdkman/env.bash")).unwrap();
+        assert!(script.contains("JAVA_HOME="));
+        assert!(script.contains("MAVEN_HOME="));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use nu_plugin_sdkman::core::api;
+    use std::fs;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::{api, version};
 
     #[test]
     fn test_parse_versions_basic() {
@@ -56,4 +59,24 @@ mod tests {
         assert_eq!(versions[1].version, "24.2.2.r24-nik");
         assert_eq!(versions[1].vendor, "nik");
     }
+
+    #[test]
+    #[serial]
+    fn test_get_cached_versions() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        assert!(api::get_cached_versions("java", "linuxx64").is_none());
+
+        let metadata = temp.path().join("var/metadata");
+        fs::create_dir_all(&metadata).unwrap();
+        fs::write(metadata.join("java-linuxx64.versions"), "21.0.9-tem,17.0.17-tem,17.0.9-zulu").unwrap();
+
+        let versions = api::get_cached_versions("java", "linuxx64").unwrap();
+        assert_eq!(versions.len(), 3);
+        assert_eq!(version::latest_release(&versions, None), Some("21.0.9-tem".to_string()));
+        assert_eq!(version::latest_release(&versions, Some("zulu")), Some("17.0.9-zulu".to_string()));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
 }
@@ -95,4 +95,24 @@ mod tests {
         let result = archive::extract_zip(&fake_archive, temp.path());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_detect_format_from_contents() {
+        let temp = tempdir().unwrap();
+        let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/test.tar.gz");
+
+        // A tar.gz served without a meaningful extension
+        let renamed = temp.path().join("download");
+        fs::copy(&fixture_path, &renamed).unwrap();
+        assert_eq!(archive::detect_format(&renamed), Some(archive::ArchiveFormat::TarGz));
+
+        archive::extract(&renamed, &temp.path().join("out")).unwrap();
+        assert!(temp.path().join("out/test-sdk/bin/test").exists());
+
+        let text = temp.path().join("notes.txt");
+        fs::write(&text, "not an archive").unwrap();
+        assert_eq!(archive::detect_format(&text), None);
+        assert!(archive::extract(&text, temp.path()).is_err());
+    }
 }
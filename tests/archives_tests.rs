@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::archives::{self, ArchiveCache};
+    use nu_plugin_sdkman::core::config::{self, PluginSettings};
+    use nu_plugin_sdkman::core::install;
+
+    fn download(dir: &Path, name: &str, size: usize) -> std::path::PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, vec![b'x'; size]).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_store_and_lookup() {
+        let cache_dir = tempdir().unwrap();
+        let tmp = tempdir().unwrap();
+        let cache = ArchiveCache { dir: cache_dir.path().to_path_buf(), max_size: 1024 };
+
+        let archive = download(tmp.path(), "java-21.0.1-tem.tar.gz", 100);
+        let checksum = archives::sha256_file(&archive).unwrap();
+        let stored = cache.store("java", "21.0.1-tem", "linuxx64", &archive).unwrap();
+
+        assert!(!archive.exists());
+        assert!(stored.to_string_lossy().ends_with("linuxx64.tar.gz"));
+        assert_eq!(cache.lookup("java", "21.0.1-tem", "linuxx64"), Some(stored));
+        assert_eq!(cache.lookup("java", "21.0.1-tem", "linuxarm64"), None);
+
+        let entries = cache.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].platform, "linuxx64");
+        assert_eq!(entries[0].sha256, checksum);
+        assert_eq!(cache.size(), 100);
+    }
+
+    #[test]
+    fn test_concurrent_stores_respect_size_limit() {
+        let cache_dir = tempdir().unwrap();
+        let tmp = tempdir().unwrap();
+        let cache = ArchiveCache { dir: cache_dir.path().to_path_buf(), max_size: 250 };
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let (cache, tmp) = (&cache, tmp.path());
+                scope.spawn(move || {
+                    let archive = download(tmp, &format!("tool-{}.tar.gz", i), 100);
+                    cache.store("tool", &format!("1.{}", i), "linuxx64", &archive).unwrap();
+                });
+            }
+        });
+
+        assert_eq!(cache.entries().len(), 2);
+        assert!(cache.size() <= 250);
+    }
+
+    #[test]
+    fn test_corrupted_archive_is_dropped() {
+        let cache_dir = tempdir().unwrap();
+        let tmp = tempdir().unwrap();
+        let cache = ArchiveCache { dir: cache_dir.path().to_path_buf(), max_size: 1024 };
+
+        let archive = download(tmp.path(), "gradle-8.5.zip", 10);
+        let stored = cache.store("gradle", "8.5", "exotic", &archive).unwrap();
+        fs::write(&stored, "truncated").unwrap();
+
+        assert_eq!(cache.lookup("gradle", "8.5", "exotic"), None);
+        assert!(cache.entries().is_empty());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache_dir = tempdir().unwrap();
+        let tmp = tempdir().unwrap();
+        let cache = ArchiveCache { dir: cache_dir.path().to_path_buf(), max_size: 250 };
+
+        cache.store("java", "17.0.9-tem", "linuxx64", &download(tmp.path(), "a.tar.gz", 100)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        cache.store("java", "21.0.1-tem", "linuxx64", &download(tmp.path(), "b.tar.gz", 100)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        cache.lookup("java", "17.0.9-tem", "linuxx64").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        cache.store("maven", "3.9.6", "exotic", &download(tmp.path(), "c.tar.gz", 100)).unwrap();
+
+        assert!(cache.lookup("java", "21.0.1-tem", "linuxx64").is_none());
+        assert!(cache.lookup("java", "17.0.9-tem", "linuxx64").is_some());
+        assert!(cache.lookup("maven", "3.9.6", "exotic").is_some());
+        assert_eq!(cache.clear().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_disabled_cache_keeps_download() {
+        let cache_dir = tempdir().unwrap();
+        let tmp = tempdir().unwrap();
+        let cache = ArchiveCache { dir: cache_dir.path().to_path_buf(), max_size: 0 };
+
+        let archive = download(tmp.path(), "a.tar.gz", 10);
+        assert_eq!(cache.store("java", "17", "linuxx64", &archive).unwrap(), archive);
+        assert_eq!(cache.lookup("java", "17", "linuxx64"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_download_to_cache_reuses_cached_archive() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path().join("sdkman"));
+        let cache_dir = temp.path().join("archives");
+
+        config::set_plugin_settings(PluginSettings { archive_dir: Some(cache_dir.clone()), archive_cache_size: Some(0), ..Default::default() });
+        assert!(install::download_to_cache("java", "21.0.1-tem", "linuxmusl").is_err());
+
+        config::set_plugin_settings(PluginSettings { archive_dir: Some(cache_dir.clone()), archive_cache_size: Some(1024), ..Default::default() });
+        let cache = ArchiveCache::open().unwrap();
+        let stored = cache.store("java", "21.0.1-tem", "linuxmusl", &download(temp.path(), "jdk.tar.gz", 10)).unwrap();
+        // Served from the cache, so no download happens
+        assert_eq!(install::download_to_cache("java", "21.0.1-tem", "linuxmusl").unwrap(), stored);
+
+        config::set_plugin_settings(PluginSettings::default());
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
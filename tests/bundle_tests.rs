@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::bundle::{self, BundleEntry};
+    use nu_plugin_sdkman::core::env;
+    use nu_plugin_sdkman::utils::archive;
+    use std::path::Path;
+
+    fn entry(candidate: &str, version: &str) -> BundleEntry {
+        BundleEntry { candidate: candidate.to_string(), version: version.to_string() }
+    }
+
+    #[test]
+    #[serial]
+    fn test_bundle_round_trip() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let out = tempdir().unwrap();
+        let file = out.path().join("toolchain.tar.gz");
+
+        // Only a bin directory: must not be unwrapped on install
+        std::env::set_var("SDKMAN_DIR", source.path());
+        let gradle = env::candidate_dir("gradle", "8.5").unwrap();
+        fs::create_dir_all(gradle.join("bin")).unwrap();
+        fs::write(gradle.join("bin/gradle"), "#!/bin/sh\n").unwrap();
+        let java = env::candidate_dir("java", "21.0.1-tem").unwrap();
+        fs::create_dir_all(java.join("bin")).unwrap();
+        fs::create_dir_all(java.join("lib")).unwrap();
+        fs::write(java.join("lib/java"), "binary").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("../lib/java", java.join("bin/java")).unwrap();
+
+        let entries = vec![entry("gradle", "8.5"), entry("java", "21.0.1-tem")];
+        let manifest = bundle::create(&file, &entries, "linuxx64").unwrap();
+        assert_eq!(manifest.entries, entries);
+        assert_eq!(bundle::read_manifest(&file).unwrap(), manifest);
+
+        std::env::set_var("SDKMAN_DIR", target.path());
+        assert!(bundle::install(&file, "darwinarm64", false).is_err());
+
+        let (_, installed) = bundle::install(&file, "linuxx64", false).unwrap();
+        assert!(installed.iter().all(|r| r.installed));
+        assert!(env::candidate_dir("gradle", "8.5").unwrap().join("bin/gradle").is_file());
+        let java = env::candidate_dir("java", "21.0.1-tem").unwrap();
+        #[cfg(unix)]
+        {
+            assert!(java.join("bin/java").is_symlink());
+            assert_eq!(fs::read_to_string(java.join("bin/java")).unwrap(), "binary");
+        }
+
+        let (_, again) = bundle::install(&file, "linuxx64", false).unwrap();
+        assert!(again.iter().all(|r| !r.installed));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_create_requires_installed_versions() {
+        let dir = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", dir.path());
+
+        let result = bundle::create(&dir.path().join("b.tar.gz"), &[entry("java", "17.0.9-tem")], "linuxx64");
+        assert!(result.is_err());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_install_rejects_entries_outside_candidates() {
+        let dir = tempdir().unwrap();
+        let sdkman = dir.path().join("sdkman");
+        let victim = dir.path().join("victim");
+        fs::create_dir_all(victim.join("data")).unwrap();
+        fs::write(victim.join("data/keep"), "keep").unwrap();
+
+        // Packs a directory the bundle claims to be ../../../victim/data
+        let packed = dir.path().join("packed");
+        fs::create_dir_all(packed.join("bin")).unwrap();
+        let manifest = dir.path().join("manifest.json");
+        fs::write(&manifest, r#"{"format":1,"platform":"linuxx64","created":"","entries":[{"candidate":"../../../victim","version":"data"}]}"#).unwrap();
+        let file = dir.path().join("evil.tar.gz");
+        archive::create_tar_gz(&file, &[
+            (manifest.as_path(), Path::new("manifest.json")),
+            (packed.as_path(), Path::new("candidates/x/data")),
+        ]).unwrap();
+
+        std::env::set_var("SDKMAN_DIR", &sdkman);
+        assert!(bundle::read_manifest(&file).is_err());
+        assert!(bundle::install(&file, "linuxx64", false).is_err());
+        assert_eq!(fs::read_to_string(victim.join("data/keep")).unwrap(), "keep");
+        assert!(!sdkman.join("candidates").exists());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
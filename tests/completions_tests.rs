@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::{completions, config, env};
+
+    #[test]
+    #[serial]
+    fn test_candidates_merge_cache_and_installed() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        fs::create_dir_all(temp.path().join("candidates/visualvm/2.1.8")).unwrap();
+
+        assert_eq!(completions::candidates(), vec!["visualvm"]);
+
+        config::write_cached_candidates(&["java".to_string(), "gradle".to_string()]).unwrap();
+        assert_eq!(completions::candidates(), vec!["gradle", "java", "visualvm"]);
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_installed_versions_newest_first() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        for version in ["11.0.21-tem", "21.0.1-tem", "17.0.9-tem"] {
+            fs::create_dir_all(temp.path().join("candidates/java").join(version)).unwrap();
+        }
+        env::set_current_version("java", "17.0.9-tem").unwrap();
+
+        assert_eq!(completions::installed_versions("java"), vec!["21.0.1-tem", "17.0.9-tem", "11.0.21-tem"]);
+        assert!(completions::installed_versions("maven").is_empty());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_installed_versions_in_include_isolated_installs() {
+        let temp = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9-tem")).unwrap();
+        fs::create_dir_all(project.path().join(".sdkman/candidates/java/21.0.1-tem")).unwrap();
+
+        assert_eq!(completions::installed_versions_in("java", project.path()), vec!["21.0.1-tem", "17.0.9-tem"]);
+        assert_eq!(completions::installed_versions("java"), vec!["17.0.9-tem"]);
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_available_versions_from_metadata_cache() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        let metadata = temp.path().join("var/metadata");
+        fs::create_dir_all(&metadata).unwrap();
+        fs::write(metadata.join("java-linuxarm64.versions"), "17.0.9-tem,21.0.1-tem,21.0.1-zulu").unwrap();
+
+        let versions = completions::available_versions("java", Some("linuxarm64"));
+        assert_eq!(versions.len(), 3);
+        assert!(versions[0].starts_with("21.0.1"));
+        assert_eq!(versions[2], "17.0.9-tem");
+
+        // Never fetched for this platform: nothing, rather than a request on TAB
+        assert!(completions::available_versions("java", Some("linuxx64")).is_empty());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::commands::config::parse_plugin_config;
+    use nu_plugin_sdkman::core::config::{self, Config, ConfigFile, EnvMode, PluginSettings};
+    use nu_protocol::{record, Value};
+
+    const BASH_CONFIG: &str = "# Managed by SDKMAN!\nsdkman_auto_answer=false\nsdkman_curl_connect_timeout=7\n\nsdkman_insecure_ssl=true\n";
+
+    #[test]
+    fn test_parse_config_file() {
+        let file = ConfigFile::parse(BASH_CONFIG);
+
+        assert_eq!(file.get("sdkman_auto_answer"), Some("false"));
+        assert_eq!(file.get("sdkman_curl_connect_timeout"), Some("7"));
+        assert_eq!(file.get("sdkman_missing"), None);
+        assert_eq!(file.entries().count(), 3);
+    }
+
+    #[test]
+    fn test_set_and_unset_preserve_layout() {
+        let mut file = ConfigFile::parse(BASH_CONFIG);
+        file.set("sdkman_auto_answer", "true");
+        file.set("sdkman_auto_env", "true");
+        assert!(file.unset("sdkman_insecure_ssl"));
+        assert!(!file.unset("sdkman_insecure_ssl"));
+
+        assert_eq!(
+            file.to_string(),
+            "# Managed by SDKMAN!\nsdkman_auto_answer=true\nsdkman_curl_connect_timeout=7\n\nsdkman_auto_env=true\n"
+        );
+    }
+
+    #[test]
+    fn test_typed_config_defaults() {
+        let config = Config::from(&ConfigFile::parse("sdkman_curl_connect_timeout=abc\nsdkman_auto_env=true\n"));
+
+        assert!(config.auto_env);
+        assert!(!config.insecure_ssl);
+        assert_eq!(config.curl_connect_timeout, 7);
+        assert_eq!(config.curl_max_time, 10);
+    }
+
+    #[test]
+    fn test_plugin_settings_override_file() {
+        let mut config = Config::from(&ConfigFile::parse("sdkman_auto_env=true\n"));
+        config.apply(PluginSettings {
+            api_base_url: Some("https://mirror.example.com/2/".to_string()),
+            offline: Some(true),
+            parallelism: Some(0),
+            auto_env: Some(false),
+            home_vars: [("jbang".to_string(), "JBANG_DIR".to_string())].into_iter().collect(),
+            env_mode: Some("isolated".parse().unwrap()),
+            ..Default::default()
+        });
+
+        assert_eq!(config.api_base_url, "https://mirror.example.com/2");
+        assert!(config.offline);
+        assert!(!config.auto_env);
+        assert_eq!(config.parallelism, 1);
+        assert_eq!(config.platform, None);
+        assert_eq!(config.home_var("jbang"), "JBANG_DIR");
+        assert_eq!(config.home_var("java"), "JAVA_HOME");
+        assert_eq!(config.home_var("spring-boot"), "SPRING_BOOT_HOME");
+        assert_eq!(config.env_mode, EnvMode::Isolated);
+        assert_eq!(Config::default().env_mode, EnvMode::Shared);
+        assert!("hermetic".parse::<EnvMode>().is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_and_save_config() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        assert_eq!(Config::load(), Config::default());
+
+        let mut file = ConfigFile::load().unwrap();
+        file.set("sdkman_auto_answer", "true");
+        file.save().unwrap();
+
+        let saved = fs::read_to_string(temp.path().join("etc/config")).unwrap();
+        assert_eq!(saved, "sdkman_auto_answer=true\n");
+        assert_eq!(ConfigFile::load().unwrap().get("sdkman_auto_answer"), Some("true"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_var_state_files() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        assert_eq!(config::cached_candidates(), None);
+
+        fs::create_dir_all(temp.path().join("var")).unwrap();
+        fs::write(temp.path().join("var/candidates"), "java,gradle,maven\n").unwrap();
+        fs::write(temp.path().join("var/version"), "5.18.2\n").unwrap();
+
+        assert_eq!(
+            config::cached_candidates(),
+            Some(vec!["java".to_string(), "gradle".to_string(), "maven".to_string()])
+        );
+        assert_eq!(config::read_var("version"), Some("5.18.2".to_string()));
+
+        config::write_cached_candidates(&["java".to_string(), "kotlin".to_string()]).unwrap();
+        assert_eq!(fs::read_to_string(temp.path().join("var/candidates")).unwrap(), "java,kotlin");
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    fn test_validate_known_keys() {
+        assert!(config::validate("sdkman_auto_answer", "true").is_ok());
+        assert!(config::validate("sdkman_curl_connect_timeout", "30").is_ok());
+
+        assert!(config::validate("sdkman_auto_answer", "yes").is_err());
+        assert!(config::validate("sdkman_curl_connect_timeout", "-1").is_err());
+        assert!(config::validate("sdkman_unknown", "true").is_err());
+    }
+
+    #[test]
+    fn test_plugin_config_ignores_unknown_keys() {
+        let value = Value::test_record(record! {
+            "offline" => Value::test_bool(true),
+            "ofline" => Value::test_bool(true),
+            "future_option" => Value::test_int(1),
+        });
+        let settings = parse_plugin_config(&value).unwrap();
+        assert_eq!(settings.offline, Some(true));
+        assert_eq!(settings.ignored_keys, vec!["ofline", "future_option"]);
+
+        let invalid = Value::test_record(record! { "offline" => Value::test_string("yes") });
+        assert!(parse_plugin_config(&invalid).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::{discover, env};
+
+    #[test]
+    fn test_parse_release_file() {
+        let release = "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"17.0.9\"\nJAVA_VERSION_DATE=\"2023-10-17\"\n";
+        assert_eq!(discover::parse_release_file(release), Some("17.0.9".to_string()));
+        assert_eq!(discover::parse_release_file("IMPLEMENTOR=\"x\"\n"), None);
+    }
+
+    #[test]
+    fn test_parse_version_output() {
+        assert_eq!(discover::parse_version_output("Apache Maven 3.9.6 (bc0240f3c744dd6b6ec2920b3cd08dcc295161ae)"), Some("3.9.6".to_string()));
+        assert_eq!(discover::parse_version_output("\n------\nGradle 8.5\n------\n"), Some("8.5".to_string()));
+        assert_eq!(discover::parse_version_output("no version here"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_inspect_and_link_jdks() {
+        let sdkman = tempdir().unwrap();
+        let jvm = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", sdkman.path());
+
+        for (dir, version) in [("java-17-openjdk", "17.0.9"), ("temurin-17", "17.0.10"), ("java-8-openjdk", "1.8.0_392")] {
+            let home = jvm.path().join(dir);
+            fs::create_dir_all(home.join("bin")).unwrap();
+            fs::write(home.join("bin/java"), "").unwrap();
+            fs::write(home.join("release"), format!("JAVA_VERSION=\"{}\"\n", version)).unwrap();
+        }
+        fs::create_dir_all(jvm.path().join("not-a-jdk")).unwrap();
+
+        let homes: Vec<_> = ["java-17-openjdk", "temurin-17", "java-8-openjdk", "not-a-jdk"]
+            .iter()
+            .map(|d| jvm.path().join(d))
+            .collect();
+        let found = discover::inspect_homes("java", "java", &homes, &[]);
+        let names: Vec<&str> = found.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["system-17", "system-17-2", "system-8"]);
+
+        discover::link("java", &found[0].name, &found[0].path).unwrap();
+        assert!(env::is_installed("java", "system-17"));
+        assert!(env::is_local_version("java", "system-17"));
+        assert!(discover::link("java", "system-17", &found[1].path).is_err());
+
+        let again = discover::inspect_homes("java", "java", &homes, &[]);
+        assert_eq!(again[0].linked_as, Some("system-17".to_string()));
+
+        // Names of versions installed from elsewhere are taken too
+        assert!(discover::link("java", "../system-9", &found[2].path).is_err());
+        assert!(discover::link("java", "current", &found[2].path).is_err());
+        assert_eq!(env::get_current_version("java"), None);
+        let other = jvm.path().join("other");
+        fs::create_dir_all(&other).unwrap();
+        discover::link("java", "system-8", &other).unwrap();
+        let renamed = discover::inspect_homes("java", "java", &homes[2..3], &[]);
+        assert_eq!(renamed[0].name, "system-8-2");
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
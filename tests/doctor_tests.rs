@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::doctor::{self, Severity};
+
+    #[test]
+    #[serial]
+    fn test_healthy_installation() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9/bin")).unwrap();
+
+        let findings = doctor::diagnose(false).unwrap();
+        let in_sdkman: Vec<_> = findings.iter()
+            .filter(|f| f.path.starts_with(temp.path()) && f.severity > Severity::Info)
+            .collect();
+        assert!(in_sdkman.is_empty(), "unexpected findings: {:?}", in_sdkman);
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_empty_installation_and_missing_bin() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        let empty = temp.path().join("candidates/java/17.0.9");
+        let no_bin = temp.path().join("candidates/java/21.0.1");
+        fs::create_dir_all(&empty).unwrap();
+        fs::create_dir_all(no_bin.join("lib")).unwrap();
+
+        let findings = doctor::diagnose(false).unwrap();
+        let empty_finding = findings.iter().find(|f| f.path == empty).unwrap();
+        assert_eq!(empty_finding.severity, Severity::Error);
+        assert!(empty_finding.fixable);
+        let no_bin_finding = findings.iter().find(|f| f.path == no_bin).unwrap();
+        assert_eq!(no_bin_finding.severity, Severity::Warning);
+        assert!(!no_bin_finding.fixable);
+
+        doctor::diagnose(true).unwrap();
+        assert!(!empty.exists());
+        assert!(no_bin.exists());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_dangling_current_link_is_fixed() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        let java = temp.path().join("candidates/java");
+        fs::create_dir_all(&java).unwrap();
+        let current = java.join("current");
+        std::os::unix::fs::symlink(java.join("17.0.9"), &current).unwrap();
+
+        let findings = doctor::diagnose(true).unwrap();
+        let finding = findings.iter().find(|f| f.path == current).unwrap();
+        assert_eq!(finding.severity, Severity::Error);
+        assert!(finding.fixed);
+        assert!(!current.is_symlink());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_only_abandoned_install_dirs_are_removed() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        let running = temp.path().join("tmp/sdkman-install-java-21.0.1");
+        let abandoned = temp.path().join("tmp/sdkman-local-java-17.0.9");
+        let other = temp.path().join("tmp/unrelated");
+        for dir in [&running, &abandoned, &other] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        let two_days_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(2 * 24 * 60 * 60);
+        for dir in [&abandoned, &other] {
+            fs::File::open(dir).unwrap().set_modified(two_days_ago).unwrap();
+        }
+
+        let findings = doctor::diagnose(true).unwrap();
+        assert!(findings.iter().any(|f| f.path == abandoned && f.fixable));
+        assert!(!findings.iter().any(|f| f.path == running || f.path == other));
+        assert!(!abandoned.exists());
+        assert!(running.exists());
+        assert!(other.exists());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::commands::du;
+    use nu_plugin_sdkman::core::config::{self, PluginSettings};
+    use nu_plugin_sdkman::utils::fs::DirSize;
+
+    #[test]
+    #[serial]
+    fn test_du_summary_counts_every_area() {
+        let temp = tempdir().unwrap();
+        let sdkman = temp.path().join("sdkman");
+        std::env::set_var("SDKMAN_DIR", &sdkman);
+        config::set_plugin_settings(PluginSettings { archive_dir: Some(temp.path().join("archives")), ..Default::default() });
+
+        let files = [
+            ("sdkman/candidates/java/17.0.9/bin/java", 100),
+            ("sdkman/candidates/java/21.0.1/bin/java", 200),
+            ("sdkman/candidates/maven/3.9.6/bin/mvn", 50),
+            ("sdkman/tmp/java-17.zip", 10),
+            ("sdkman/var/candidates", 5),
+            ("archives/java/17.0.9/linuxx64.tar.gz", 1000),
+        ];
+        for (path, len) in files {
+            let path = temp.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![0u8; len]).unwrap();
+        }
+
+        let summary = du::summary(&["java".to_string(), "maven".to_string()]).unwrap();
+        let size = |name: &str| summary.iter().find(|(n, _)| n == name).map(|(_, s)| *s).unwrap();
+
+        assert_eq!(size("java"), DirSize { bytes: 300, files: 2 });
+        assert_eq!(size("maven"), DirSize { bytes: 50, files: 1 });
+        assert_eq!(size("tmp"), DirSize { bytes: 10, files: 1 });
+        assert_eq!(size("var"), DirSize { bytes: 5, files: 1 });
+        assert_eq!(size("archives"), DirSize { bytes: 1000, files: 1 });
+        assert_eq!(summary.last().unwrap(), &("total".to_string(), DirSize { bytes: 1365, files: 6 }));
+
+        config::set_plugin_settings(PluginSettings::default());
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
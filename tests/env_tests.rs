@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use nu_plugin_sdkman::commands::env;
+    use nu_plugin_sdkman::core::env as core_env;
 
     #[test]
     fn test_parse_sdkmanrc_basic() {
@@ -62,4 +63,36 @@ mod tests {
         
         assert_eq!(parsed.len(), 0);
     }
+
+    #[test]
+    fn test_platform_id() {
+        assert_eq!(core_env::platform_id("linux", "x86_64", false, false), "linuxx64");
+        assert_eq!(core_env::platform_id("linux", "x86_64", true, false), "linuxmusl");
+        assert_eq!(core_env::platform_id("linux", "arm", false, true), "linuxarm32hf");
+        assert_eq!(core_env::platform_id("linux", "arm", false, false), "linuxarm32sf");
+        assert_eq!(core_env::platform_id("linux", "x86", false, false), "linuxx32");
+        assert_eq!(core_env::platform_id("macos", "aarch64", false, false), "darwinarm64");
+        assert_eq!(core_env::platform_id("freebsd", "x86_64", false, false), "exotic");
+    }
+
+    #[test]
+    fn test_musl_loader_next_to_glibc_is_not_musl() {
+        let lib = tempfile::tempdir().unwrap();
+        let lib64 = tempfile::tempdir().unwrap();
+        let dirs = [lib.path(), lib64.path()];
+        assert!(!core_env::is_musl_system(&dirs));
+
+        std::fs::write(lib.path().join("ld-musl-x86_64.so.1"), "").unwrap();
+        assert!(core_env::is_musl_system(&dirs));
+
+        // glibc distribution with the musl package installed
+        std::fs::write(lib64.path().join("ld-linux-x86-64.so.2"), "").unwrap();
+        assert!(!core_env::is_musl_system(&dirs));
+    }
+
+    #[test]
+    fn test_validate_platform() {
+        assert_eq!(core_env::validate_platform(" LinuxMusl ").unwrap(), "linuxmusl");
+        assert!(core_env::validate_platform("linuxriscv").is_err());
+    }
 }
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io::Read;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::exec;
+
+    #[test]
+    #[serial]
+    fn test_resolve_toolchain_from_sdkmanrc_and_specs() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path().join("global"));
+        fs::create_dir_all(temp.path().join("global/candidates/java/17.0.9")).unwrap();
+        fs::create_dir_all(temp.path().join("global/candidates/java/21.0.1")).unwrap();
+        fs::create_dir_all(temp.path().join("global/candidates/maven/3.9.6")).unwrap();
+
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join(".sdkmanrc"), "java=17.0.9\nmaven=3.9.6\n").unwrap();
+
+        let pinned = exec::resolve_toolchain(&[], &project).unwrap();
+        let versions: Vec<(&str, &str)> = pinned.iter().map(|t| (t.candidate.as_str(), t.version.as_str())).collect();
+        assert_eq!(versions, vec![("java", "17.0.9"), ("maven", "3.9.6")]);
+
+        // Explicit specs override the pins
+        let overridden = exec::resolve_toolchain(&["java@21.0.1".to_string()], &project).unwrap();
+        let java = overridden.iter().find(|t| t.candidate == "java").unwrap();
+        assert_eq!(java.version, "21.0.1");
+        assert_eq!(java.home, temp.path().join("global/candidates/java/21.0.1"));
+
+        let err = exec::resolve_toolchain(&["java@8.0.0".to_string()], &project).unwrap_err();
+        assert!(err.to_string().contains("not installed"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_resolve_toolchain_from_subdirectory_and_local_link() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path().join("global"));
+        fs::create_dir_all(temp.path().join("global/candidates/maven/3.9.6")).unwrap();
+        fs::create_dir_all(temp.path().join("global/candidates/java/21.0.1")).unwrap();
+        std::os::unix::fs::symlink("21.0.1", temp.path().join("global/candidates/java/current")).unwrap();
+
+        let project = temp.path().join("project");
+        let subdir = project.join("module/src");
+        fs::create_dir_all(&subdir).unwrap();
+        fs::write(project.join(".sdkmanrc"), "maven=3.9.6\n").unwrap();
+        let local = project.join(".sdkman/candidates/java");
+        fs::create_dir_all(local.join("17.0.9")).unwrap();
+        std::os::unix::fs::symlink("17.0.9", local.join("current")).unwrap();
+
+        // Pins of the parent's .sdkmanrc apply; a bare spec uses the local link
+        let toolchain = exec::resolve_toolchain(&["java".to_string()], &subdir).unwrap();
+        let versions: Vec<(&str, &str)> = toolchain.iter().map(|t| (t.candidate.as_str(), t.version.as_str())).collect();
+        assert_eq!(versions, vec![("maven", "3.9.6"), ("java", "17.0.9")]);
+        assert_eq!(toolchain[1].home, local.join("17.0.9"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_exec_uses_toolchain_and_reports_exit_code() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        let bin = temp.path().join("candidates/java/17.0.9/bin");
+        fs::create_dir_all(&bin).unwrap();
+        let java = bin.join("java");
+        fs::write(&java, "#!/bin/sh\necho \"java 17 home=$JAVA_HOME\"\nexit ${EXIT:-0}\n").unwrap();
+        fs::set_permissions(&java, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+
+        let toolchain = exec::resolve_toolchain(&["java@17.0.9".to_string()], temp.path()).unwrap();
+        let base: HashMap<String, String> = [("PATH".to_string(), "/usr/bin:/bin".to_string())].into_iter().collect();
+        let vars = exec::command_env(&toolchain, &base).unwrap();
+        assert_eq!(vars["PATH"], format!("{}:/usr/bin:/bin", bin.display()));
+
+        let mut output = String::new();
+        exec::spawn("java", &[], temp.path(), &vars, None).unwrap().read_to_string(&mut output).unwrap();
+        assert_eq!(output.trim(), format!("java 17 home={}", temp.path().join("candidates/java/17.0.9").display()));
+
+        let mut failing = vars.clone();
+        failing.insert("EXIT".to_string(), "3".to_string());
+        let err = exec::spawn("java", &[], temp.path(), &failing, None).unwrap().read_to_string(&mut output).unwrap_err();
+        assert!(err.to_string().contains("exited with code 3"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
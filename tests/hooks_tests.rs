@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::{env, hooks, install};
+
+    #[test]
+    fn test_macos_jdk_layout_fixup() {
+        let temp = tempdir().unwrap();
+        let install_dir = temp.path().join("21.0.1-tem");
+        fs::create_dir_all(install_dir.join("Contents/Home/bin")).unwrap();
+        fs::write(install_dir.join("Contents/Info.plist"), "").unwrap();
+
+        let reports = hooks::builtin_fixups("java", &install_dir).unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].name, "macos-jdk-layout");
+        assert!(install_dir.join("bin").is_dir());
+        assert!(!install_dir.join("Contents").exists());
+        assert!(!temp.path().join("21.0.1-tem.bundle").exists());
+
+        // Already normalized installations are left alone
+        assert!(hooks::builtin_fixups("java", &install_dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_nested_archive_fixup() {
+        let temp = tempdir().unwrap();
+        let install_dir = temp.path().join("1.0.0");
+        fs::create_dir_all(&install_dir).unwrap();
+        let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/test.tar.gz");
+        fs::copy(&fixture_path, install_dir.join("inner.tar.gz")).unwrap();
+
+        let reports = hooks::builtin_fixups("testsdk", &install_dir).unwrap();
+
+        assert_eq!(reports[0].name, "nested-archive");
+        assert!(install_dir.join("bin/test").exists());
+        assert!(!install_dir.join("inner.tar.gz").exists());
+    }
+
+    #[test]
+    fn test_single_jar_is_not_extracted() {
+        let temp = tempdir().unwrap();
+        let install_dir = temp.path().join("1.0.0");
+        fs::create_dir_all(&install_dir).unwrap();
+        let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/test.zip");
+        fs::copy(&fixture_path, install_dir.join("app.jar")).unwrap();
+
+        assert!(hooks::builtin_fixups("testsdk", &install_dir).unwrap().is_empty());
+        assert!(install_dir.join("app.jar").is_file());
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_user_hooks_run_in_order() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        let hooks_dir = hooks::hooks_dir().unwrap().join("testsdk");
+        fs::create_dir_all(&hooks_dir).unwrap();
+        fs::write(hooks_dir.join("10-first.sh"), "echo \"$SDKMAN_CANDIDATE $SDKMAN_VERSION\"\ntouch marker\n").unwrap();
+        fs::write(hooks_dir.join("20-second.sh"), "test -f marker && echo second\n").unwrap();
+
+        let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/test.tar.gz");
+        let reports = install::install_local("testsdk", "1.0.0", &fixture_path).unwrap();
+
+        let lines: Vec<String> = reports.iter().map(|r| r.to_string()).collect();
+        assert_eq!(lines, vec!["[hook 10-first.sh] testsdk 1.0.0", "[hook 20-second.sh] second"]);
+
+        // A failing hook aborts the install and removes the version
+        fs::write(hooks_dir.join("30-fail.sh"), "echo broken >&2\nexit 3\n").unwrap();
+        let err = install::install_local("testsdk", "2.0.0", &fixture_path).unwrap_err();
+        assert!(err.to_string().contains("30-fail.sh"));
+        assert!(err.to_string().contains("broken"));
+        assert!(!env::is_installed("testsdk", "2.0.0"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
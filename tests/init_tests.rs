@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::init::{self, InitCandidate, InitModule, GENERATED_HEADER};
+
+    fn module(auto_env: bool) -> InitModule {
+        InitModule {
+            sdkman_dir: PathBuf::from("/home/user/.sdkman"),
+            candidates: vec![
+                InitCandidate { candidate: "java".into(), home_var: "JAVA_HOME".into() },
+                InitCandidate { candidate: "gradle".into(), home_var: "GRADLE_HOME".into() },
+            ],
+            auto_env,
+        }
+    }
+
+    #[test]
+    fn test_render_paths_and_home_vars() {
+        let script = module(false).render();
+
+        assert!(script.starts_with(GENERATED_HEADER));
+        assert!(script.contains("$env.SDKMAN_DIR = r#'/home/user/.sdkman'#"));
+        assert!(script.contains("r#'/home/user/.sdkman/candidates/java/current/bin'#"));
+        assert!(script.contains("r#'/home/user/.sdkman/candidates/gradle/current/bin'#"));
+        assert!(script.contains("$env.JAVA_HOME = r#'/home/user/.sdkman/candidates/java/current'#"));
+        assert!(script.contains("$env.GRADLE_HOME = r#'/home/user/.sdkman/candidates/gradle/current'#"));
+        assert!(script.contains("export def \"nu-complete sdk candidates\""));
+        assert!(!script.contains("env_change"));
+    }
+
+    #[test]
+    fn test_render_auto_env_hook() {
+        let script = module(true).render();
+        assert!(script.contains("upsert hooks.env_change.PWD"));
+        assert!(script.contains(".sdkmanrc"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_refresh_only_rewrites_generated_module() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.0")).unwrap();
+
+        let path = init::init_script_path().unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        // Hand-written scripts are left alone
+        fs::write(&path, "# my own init\n").unwrap();
+        init::refresh_init_script();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# my own init\n");
+
+        // Generated modules pick up newly installed candidates
+        fs::write(&path, format!("{}\n", GENERATED_HEADER)).unwrap();
+        init::refresh_init_script();
+        let script = fs::read_to_string(&path).unwrap();
+        assert!(script.contains("JAVA_HOME"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}
@@ -4,7 +4,7 @@ mod tests {
     use std::path::Path;
     use tempfile::tempdir;
     use serial_test::serial;
-    use nu_plugin_sdkman::core::env;
+    use nu_plugin_sdkman::core::{env, install};
     use nu_plugin_sdkman::utils::archive;
 
     #[test]
@@ -83,4 +83,49 @@ mod tests {
 
         std::env::remove_var("SDKMAN_DIR");
     }
+
+    #[test]
+    #[serial]
+    fn test_install_from_archive_and_directory() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/test.tar.gz");
+        let source = install::InstallSource::parse(fixture_path.to_str().unwrap(), temp.path()).unwrap();
+        install::install_from("testsdk", "1.0.0-local", &source).unwrap();
+
+        assert!(temp.path().join("candidates/testsdk/1.0.0-local/bin/test").exists());
+        assert!(env::is_local_version("testsdk", "1.0.0-local"));
+
+        let existing = tempdir().unwrap();
+        fs::create_dir_all(existing.path().join("bin")).unwrap();
+        let source = install::InstallSource::parse(existing.path().to_str().unwrap(), temp.path()).unwrap();
+        assert!(matches!(source, install::InstallSource::Directory(_)));
+        install::install_from("testsdk", "system", &source).unwrap();
+
+        assert!(env::is_installed("testsdk", "system"));
+        assert!(env::is_local_version("testsdk", "system"));
+        #[cfg(unix)]
+        assert!(temp.path().join("candidates/testsdk/system").is_symlink());
+
+        fs::create_dir_all(temp.path().join("candidates/testsdk/2.0.0")).unwrap();
+        assert!(!env::is_local_version("testsdk", "2.0.0"));
+        assert!(install::InstallSource::parse("/does/not/exist", temp.path()).is_err());
+
+        // A failed extraction leaves no staging directory to be reused by a retry
+        let broken = existing.path().join("broken.tar.gz");
+        fs::write(&broken, "not an archive").unwrap();
+        assert!(install::install_from("testsdk", "3.0.0", &install::InstallSource::Archive(broken)).is_err());
+        let staging: Vec<_> = fs::read_dir(temp.path().join("tmp")).unwrap().collect();
+        assert!(staging.is_empty());
+
+        // Relative paths are resolved against the given directory, not the process cwd
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+        let source = install::InstallSource::parse("fixtures/test.tar.gz", &fixtures).unwrap();
+        assert!(matches!(source, install::InstallSource::Archive(path) if path == fixture_path.canonicalize().unwrap()));
+        assert!(install::InstallSource::parse("fixtures/test.tar.gz", existing.path()).is_err());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
 }
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::{env, prune};
+    use nu_plugin_sdkman::core::prune::{InstalledVersion, PrunePolicy};
+    use nu_plugin_sdkman::utils;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn installed(now: SystemTime, versions: &[(&str, u64)]) -> Vec<InstalledVersion> {
+        versions
+            .iter()
+            .map(|(v, age_days)| InstalledVersion {
+                version: v.to_string(),
+                installed_at: Some(now - DAY * (*age_days as u32)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_keep_newest_versions() {
+        let now = SystemTime::now();
+        let versions = installed(now, &[("17.0.9-tem", 1), ("21.0.1-tem", 1), ("11.0.21-tem", 1), ("17.0.10-tem", 1)]);
+        let policy = PrunePolicy { keep: Some(2), older_than: None };
+
+        let removed = prune::select_prunable(&versions, &HashSet::new(), &policy, now);
+        assert_eq!(removed, vec!["17.0.9-tem".to_string(), "11.0.21-tem".to_string()]);
+    }
+
+    #[test]
+    fn test_protected_versions_are_kept() {
+        let now = SystemTime::now();
+        let versions = installed(now, &[("21.0.1-tem", 1), ("17.0.9-tem", 1), ("11.0.21-tem", 1)]);
+        let protected: HashSet<String> = ["11.0.21-tem".to_string()].into();
+        let policy = PrunePolicy { keep: Some(1), older_than: None };
+
+        let removed = prune::select_prunable(&versions, &protected, &policy, now);
+        assert_eq!(removed, vec!["17.0.9-tem".to_string()]);
+    }
+
+    #[test]
+    fn test_older_than() {
+        let now = SystemTime::now();
+        let versions = installed(now, &[("21.0.1-tem", 10), ("17.0.9-tem", 120), ("11.0.21-tem", 200)]);
+        let policy = PrunePolicy { keep: None, older_than: Some(DAY * 90) };
+
+        let removed = prune::select_prunable(&versions, &HashSet::new(), &policy, now);
+        assert_eq!(removed, vec!["17.0.9-tem".to_string(), "11.0.21-tem".to_string()]);
+
+        let policy = PrunePolicy { keep: Some(2), older_than: Some(DAY * 90) };
+        let removed = prune::select_prunable(&versions, &HashSet::new(), &policy, now);
+        assert_eq!(removed, vec!["11.0.21-tem".to_string()]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_protected_versions_include_current_and_sdkmanrc() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9")).unwrap();
+        fs::create_dir_all(temp.path().join("candidates/java/21.0.1")).unwrap();
+        env::set_current_version("java", "21.0.1").unwrap();
+
+        let rc = temp.path().join("project.sdkmanrc");
+        fs::write(&rc, "java=17.0.9\ngradle=8.5\n").unwrap();
+
+        let protected = prune::protected_versions("java", &[rc.as_path()]);
+        assert!(protected.contains("21.0.1"));
+        assert!(protected.contains("17.0.9"));
+        assert!(!protected.contains("8.5"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    fn test_dir_size() {
+        let temp = tempdir().unwrap();
+        fs::create_dir_all(temp.path().join("bin")).unwrap();
+        fs::write(temp.path().join("bin/java"), vec![0u8; 100]).unwrap();
+        fs::write(temp.path().join("release"), vec![0u8; 20]).unwrap();
+
+        let size = utils::fs::dir_size(temp.path()).unwrap();
+        assert_eq!(size.bytes, 120);
+        assert_eq!(size.files, 2);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::{env, shims};
+    use nu_plugin_sdkman::core::which::{self, BinarySource};
+
+    fn install(sdkman: &Path, candidate: &str, version: &str, binaries: &[&str]) {
+        let bin = sdkman.join("candidates").join(candidate).join(version).join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        for binary in binaries {
+            fs::write(bin.join(binary), "").unwrap();
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_rehash_writes_shims_and_removes_stale_ones() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        install(temp.path(), "java", "17.0.0", &["java", "javac"]);
+        install(temp.path(), "java", "21.0.1", &["java", "jwebserver"]);
+        install(temp.path(), "maven", "3.9.6", &["mvn"]);
+
+        let dir = shims::shims_dir().unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("my-tool"), "#!/bin/sh\n").unwrap();
+
+        let plugin = temp.path().join("nu_plugin_sdkman");
+        let shims = shims::rehash(&plugin).unwrap();
+        let names: Vec<&str> = shims.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["java", "javac", "jwebserver", "mvn"]);
+        assert_eq!(shims[3].candidate, "maven");
+        assert!(shims.iter().all(|s| s.shadowed.is_empty()));
+
+        #[cfg(unix)]
+        {
+            let script = fs::read_to_string(dir.join("mvn")).unwrap();
+            assert!(script.contains(&format!("'{}' {} 'maven' 'mvn' \"$@\"", plugin.display(), shims::SHIM_FLAG)));
+        }
+
+        // Shims of uninstalled binaries go away; files we didn't write stay
+        fs::remove_dir_all(temp.path().join("candidates/maven")).unwrap();
+        let shims = shims::rehash(&plugin).unwrap();
+        assert_eq!(shims.len(), 3);
+        assert_eq!(shims::list().unwrap(), shims);
+        assert!(dir.join("my-tool").exists());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_uses_nearest_sdkmanrc_then_default() {
+        let temp = tempdir().unwrap();
+        let sdkman = temp.path().join("sdkman");
+        std::env::set_var("SDKMAN_DIR", &sdkman);
+        install(&sdkman, "java", "17.0.0", &["java"]);
+        install(&sdkman, "java", "21.0.1", &["java"]);
+        env::set_current_version("java", "21.0.1").unwrap();
+
+        let project = temp.path().join("project");
+        let nested = project.join("src/main");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(project.join(".sdkmanrc"), "java=17.0.0\n").unwrap();
+
+        let (version, home) = shims::resolve("java", &nested).unwrap();
+        assert_eq!(version, "17.0.0");
+        assert_eq!(home, sdkman.join("candidates/java/17.0.0"));
+
+        let (version, _) = shims::resolve("java", temp.path()).unwrap();
+        assert_eq!(version, "21.0.1");
+
+        // Isolated installs of the pinning project win
+        fs::create_dir_all(project.join(".sdkman/candidates/java/17.0.0")).unwrap();
+        let (_, home) = shims::resolve("java", &nested).unwrap();
+        assert_eq!(home, project.join(".sdkman/candidates/java/17.0.0"));
+
+        fs::write(project.join(".sdkmanrc"), "java=11.0.0\n").unwrap();
+        assert!(shims::resolve("java", &nested).is_err());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_shared_binaries_are_reported_as_shadowed() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        install(temp.path(), "java", "21.0.1", &["java"]);
+        install(temp.path(), "kotlin", "1.9.22", &["kotlin", "java"]);
+
+        let shims = shims::rehash(&temp.path().join("nu_plugin_sdkman")).unwrap();
+        let java = shims.iter().find(|s| s.name == "java").unwrap();
+        assert_eq!(java.candidate, "java");
+        assert_eq!(java.shadowed, vec!["kotlin".to_string()]);
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_which_resolves_shims() {
+        let temp = tempdir().unwrap();
+        let sdkman = temp.path().join("sdkman");
+        std::env::set_var("SDKMAN_DIR", &sdkman);
+        install(&sdkman, "java", "17.0.0", &["java"]);
+        install(&sdkman, "java", "21.0.1", &["java"]);
+        env::set_current_version("java", "21.0.1").unwrap();
+        shims::rehash(&temp.path().join("nu_plugin_sdkman")).unwrap();
+
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join(".sdkmanrc"), "java=17.0.0\n").unwrap();
+
+        let dir = shims::shims_dir().unwrap();
+        let found = which::which("java", dir.to_str(), &project);
+        assert_eq!(found[0].source, BinarySource::Shim);
+        assert_eq!(found[0].path, dir.join("java"));
+        assert_eq!(found[0].candidate.as_deref(), Some("java"));
+        assert_eq!(found[0].version.as_deref(), Some("17.0.0"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_shim_runs_with_every_pinned_candidate() {
+        use std::os::unix::fs::PermissionsExt;
+        let temp = tempdir().unwrap();
+        let sdkman = temp.path().join("sdkman");
+        install(&sdkman, "maven", "3.9.6", &[]);
+        let java = sdkman.join("candidates/java/17.0.0/bin/java");
+        fs::create_dir_all(java.parent().unwrap()).unwrap();
+        fs::write(&java, "#!/bin/sh\necho \"$JAVA_HOME $MAVEN_HOME\"\n").unwrap();
+        fs::set_permissions(&java, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join(".sdkmanrc"), "java=17.0.0\nmaven=3.9.6\n").unwrap();
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_nu_plugin_sdkman"))
+            .args([shims::SHIM_FLAG, "java", "java"])
+            .current_dir(&project)
+            .env("SDKMAN_DIR", &sdkman)
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            format!("{} {}", sdkman.join("candidates/java/17.0.0").display(), sdkman.join("candidates/maven/3.9.6").display())
+        );
+    }
+}
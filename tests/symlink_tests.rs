@@ -3,7 +3,10 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
     use serial_test::serial;
-    use nu_plugin_sdkman::core::env;
+    use nu_plugin_sdkman::core::config::{self, PluginSettings};
+    use nu_plugin_sdkman::core::{doctor, env};
+    use nu_plugin_sdkman::utils::fs::relative_path;
+    use std::path::Path;
 
     #[test]
     #[serial]
@@ -104,4 +107,319 @@ mod tests {
 
         std::env::remove_var("SDKMAN_DIR");
     }
+
+    #[test]
+    #[serial]
+    fn test_local_current_version() {
+        let temp = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let original_pwd = std::env::var("PWD").ok();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        std::env::set_var("PWD", project.path());
+
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9")).unwrap();
+        assert_eq!(env::get_local_current_version("java"), None);
+
+        fs::create_dir_all(project.path().join(".sdkman")).unwrap();
+        env::set_local_current_version("java", "17.0.9").unwrap();
+
+        assert_eq!(env::get_local_current_version("java"), Some("17.0.9".to_string()));
+        assert_eq!(env::get_current_version("java"), None);
+
+        if let Some(pwd) = original_pwd {
+            std::env::set_var("PWD", pwd);
+        }
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_local_current_version_prefers_isolated_install() {
+        let temp = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let original_pwd = std::env::var("PWD").ok();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        std::env::set_var("PWD", project.path());
+
+        let local_dir = project.path().join(".sdkman");
+        let isolated = env::local_candidate_dir(&local_dir, "java", "21.0.1");
+        fs::create_dir_all(&isolated).unwrap();
+        assert!(!env::is_installed("java", "21.0.1"));
+        assert!(env::is_installed_locally("java", "21.0.1"));
+
+        env::set_local_current_version("java", "21.0.1").unwrap();
+
+        let link = local_dir.join("candidates/java/current");
+        assert_eq!(fs::read_link(&link).unwrap(), isolated);
+        assert_eq!(env::get_local_current_version("java"), Some("21.0.1".to_string()));
+
+        if let Some(pwd) = original_pwd {
+            std::env::set_var("PWD", pwd);
+        }
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_active_version_prefers_local_environment() {
+        let temp = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let original_pwd = std::env::var("PWD").ok();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        std::env::set_var("PWD", project.path());
+
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9")).unwrap();
+        fs::create_dir_all(temp.path().join("candidates/java/21.0.1")).unwrap();
+        fs::create_dir_all(temp.path().join("candidates/maven/3.9.6")).unwrap();
+        env::set_current_version("java", "17.0.9").unwrap();
+        assert_eq!(env::active_version("maven", project.path()), None);
+
+        let global = env::active_version("java", project.path()).unwrap();
+        assert_eq!(global.version, "17.0.9");
+        assert_eq!(global.source, env::VersionSource::Global);
+        assert_eq!(global.home, temp.path().join("candidates/java/17.0.9"));
+
+        fs::create_dir_all(project.path().join(".sdkman")).unwrap();
+        env::set_local_current_version("java", "21.0.1").unwrap();
+
+        let local = env::active_version("java", project.path()).unwrap();
+        assert_eq!(local.version, "21.0.1");
+        assert_eq!(local.source, env::VersionSource::Local);
+        assert_eq!(local.home, temp.path().join("candidates/java/21.0.1"));
+
+        let active = env::active_versions(project.path());
+        assert_eq!(active, vec![local]);
+
+        if let Some(pwd) = original_pwd {
+            std::env::set_var("PWD", pwd);
+        }
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_pin_status_from_project_subdirectory() {
+        let temp = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let original_pwd = std::env::var("PWD").ok();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        std::env::set_var("PWD", project.path());
+
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9")).unwrap();
+        fs::create_dir_all(temp.path().join("candidates/java/21.0.1")).unwrap();
+        fs::create_dir_all(temp.path().join("candidates/gradle/8.5")).unwrap();
+        env::set_current_version("java", "17.0.9").unwrap();
+        env::set_current_version("gradle", "8.5").unwrap();
+        fs::create_dir_all(project.path().join(".sdkman")).unwrap();
+        env::set_local_current_version("java", "21.0.1").unwrap();
+        fs::write(project.path().join(".sdkmanrc"), "java=17.0.9\nmaven=3.9.6\n").unwrap();
+
+        let subdir = project.path().join("module/src");
+        fs::create_dir_all(&subdir).unwrap();
+
+        // The local link next to the parent's .sdkmanrc is in use
+        let java = env::pin_status("java", &subdir);
+        assert_eq!(java.active.as_ref().map(|a| a.version.as_str()), Some("21.0.1"));
+        assert_eq!(java.pinned.as_deref(), Some("17.0.9"));
+        assert!(!java.matches_pin());
+
+        let statuses: Vec<(String, Option<String>, Option<String>, bool)> = env::pin_statuses(&subdir)
+            .into_iter()
+            .map(|s| (s.candidate.clone(), s.active.as_ref().map(|a| a.version.clone()), s.pinned.clone(), s.matches_pin()))
+            .collect();
+        assert_eq!(statuses, vec![
+            ("gradle".to_string(), Some("8.5".to_string()), None, true),
+            ("java".to_string(), Some("21.0.1".to_string()), Some("17.0.9".to_string()), false),
+            ("maven".to_string(), None, Some("3.9.6".to_string()), false),
+        ]);
+
+        env::set_local_current_version("java", "17.0.9").unwrap();
+        assert!(env::pin_status("java", &subdir).matches_pin());
+
+        if let Some(pwd) = original_pwd {
+            std::env::set_var("PWD", pwd);
+        }
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_version_home_prefers_isolated_install() {
+        let temp = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9")).unwrap();
+        let isolated = env::local_candidate_dir(&project.path().join(".sdkman"), "java", "21.0.1");
+        fs::create_dir_all(&isolated).unwrap();
+
+        assert_eq!(env::installed_versions_in("java", project.path()), vec!["17.0.9", "21.0.1"]);
+        assert_eq!(env::version_home("java", "21.0.1", project.path()), Some(isolated.clone()));
+        assert_eq!(
+            env::version_home("java", "17.0.9", project.path()),
+            Some(temp.path().join("candidates/java/17.0.9"))
+        );
+        assert_eq!(env::version_home("java", "11.0.1", project.path()), None);
+
+        // From a subdirectory, the project is the one of the nearest .sdkmanrc
+        let subdir = project.path().join("module/src");
+        fs::create_dir_all(&subdir).unwrap();
+        assert_eq!(env::version_home("java", "21.0.1", &env::project_root(&subdir)), None);
+        fs::write(project.path().join(".sdkmanrc"), "java=21.0.1\n").unwrap();
+        let root = env::project_root(&subdir);
+        assert_eq!(root, project.path());
+        assert_eq!(env::installed_versions_in("java", &root), vec!["17.0.9", "21.0.1"]);
+        assert_eq!(env::version_home("java", "21.0.1", &root), Some(isolated));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    fn relative_links(enabled: bool) {
+        config::set_plugin_settings(PluginSettings { relative_links: Some(enabled), ..Default::default() });
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path(Path::new("/a/b"), Path::new("/a/b/c")), Path::new("c"));
+        assert_eq!(relative_path(Path::new("/a/b/c"), Path::new("/a/d")), Path::new("../../d"));
+        assert_eq!(relative_path(Path::new("/a"), Path::new("/a")), Path::new(""));
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_relative_current_link_survives_relocation() {
+        let temp = tempdir().unwrap();
+        let original = temp.path().join("original");
+        std::env::set_var("SDKMAN_DIR", &original);
+        fs::create_dir_all(original.join("candidates/java/17.0.9/bin")).unwrap();
+
+        relative_links(true);
+        env::set_current_version("java", "17.0.9").unwrap();
+        relative_links(false);
+
+        let link = original.join("candidates/java/current");
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("17.0.9"));
+
+        // Move the whole SDKMAN directory, e.g. a mount at another path
+        let moved = temp.path().join("moved");
+        fs::rename(&original, &moved).unwrap();
+        std::env::set_var("SDKMAN_DIR", &moved);
+
+        assert!(moved.join("candidates/java/current/bin").is_dir());
+        assert_eq!(env::get_current_version("java"), Some("17.0.9".to_string()));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_absolute_current_link_breaks_on_relocation() {
+        let temp = tempdir().unwrap();
+        let original = temp.path().join("original");
+        std::env::set_var("SDKMAN_DIR", &original);
+        fs::create_dir_all(original.join("candidates/java/17.0.9")).unwrap();
+
+        env::set_current_version("java", "17.0.9").unwrap();
+        assert!(fs::read_link(original.join("candidates/java/current")).unwrap().is_absolute());
+
+        let moved = temp.path().join("moved");
+        fs::rename(&original, &moved).unwrap();
+        std::env::set_var("SDKMAN_DIR", &moved);
+
+        assert_eq!(env::get_current_version("java"), None);
+        let findings = doctor::diagnose(false).unwrap();
+        assert!(findings.iter().any(|f| f.issue.contains("missing version")));
+
+        // Re-linking after the move works despite the dangling link
+        relative_links(true);
+        env::set_current_version("java", "17.0.9").unwrap();
+        relative_links(false);
+        assert_eq!(env::get_current_version("java"), Some("17.0.9".to_string()));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_copied_project_keeps_relative_local_links() {
+        let temp = tempdir().unwrap();
+        let original_pwd = std::env::var("PWD").ok();
+        std::env::set_var("SDKMAN_DIR", temp.path().join("global"));
+
+        let project = temp.path().join("project");
+        let local_dir = project.join(".sdkman");
+        fs::create_dir_all(env::local_candidate_dir(&local_dir, "java", "21.0.1").join("bin")).unwrap();
+        std::env::set_var("PWD", &project);
+
+        relative_links(true);
+        env::set_local_current_version("java", "21.0.1").unwrap();
+        relative_links(false);
+
+        let copy = temp.path().join("copy");
+        fs::rename(&project, &copy).unwrap();
+        std::env::set_var("PWD", &copy);
+
+        assert!(copy.join(".sdkman/candidates/java/current/bin").is_dir());
+        assert_eq!(env::get_local_current_version("java"), Some("21.0.1".to_string()));
+
+        if let Some(pwd) = original_pwd {
+            std::env::set_var("PWD", pwd);
+        }
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_doctor_converts_absolute_links_when_relative_enabled() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9/bin")).unwrap();
+        env::set_current_version("java", "17.0.9").unwrap();
+
+        relative_links(true);
+        let findings = doctor::diagnose(true).unwrap();
+        relative_links(false);
+
+        assert!(findings.iter().any(|f| f.issue.contains("absolute") && f.fixed));
+        let link = temp.path().join("candidates/java/current");
+        assert_eq!(fs::read_link(link).unwrap(), Path::new("17.0.9"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_relative_links_keep_linked_version_name() {
+        let temp = tempdir().unwrap();
+        let jdk = temp.path().join("jvm/java-17-openjdk");
+        fs::create_dir_all(jdk.join("bin")).unwrap();
+        let sdkman = temp.path().join("sdkman");
+        std::env::set_var("SDKMAN_DIR", &sdkman);
+        fs::create_dir_all(sdkman.join("candidates/java")).unwrap();
+        std::os::unix::fs::symlink(&jdk, sdkman.join("candidates/java/system-17")).unwrap();
+
+        relative_links(true);
+        env::set_current_version("java", "system-17").unwrap();
+        relative_links(false);
+
+        let link = sdkman.join("candidates/java/current");
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("system-17"));
+        assert_eq!(env::get_current_version("java"), Some("system-17".to_string()));
+
+        // The doctor repair of an absolute link keeps the name as well
+        env::set_current_version("java", "system-17").unwrap();
+        relative_links(true);
+        let findings = doctor::diagnose(true).unwrap();
+        relative_links(false);
+        assert!(findings.iter().any(|f| f.issue.contains("absolute") && f.fixed));
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("system-17"));
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
 }
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::{tempdir, TempDir};
+    use serial_test::serial;
+    use nu_plugin_sdkman::commands::uninstall::release_current;
+    use nu_plugin_sdkman::core::env;
+
+    /// Installs java 17 and 21 with 17 current, and a project with a local
+    /// `.sdkman` that PWD points at.
+    fn setup() -> (TempDir, TempDir, Option<String>) {
+        let temp = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        let original_pwd = std::env::var("PWD").ok();
+        std::env::set_var("SDKMAN_DIR", temp.path());
+        std::env::set_var("PWD", project.path());
+
+        fs::create_dir_all(temp.path().join("candidates/java/17.0.9")).unwrap();
+        fs::create_dir_all(temp.path().join("candidates/java/21.0.1")).unwrap();
+        env::set_current_version("java", "17.0.9").unwrap();
+        (temp, project, original_pwd)
+    }
+
+    fn teardown(original_pwd: Option<String>) {
+        if let Some(pwd) = original_pwd {
+            std::env::set_var("PWD", pwd);
+        }
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    fn removing(version: &str) -> Vec<String> {
+        vec![version.to_string()]
+    }
+
+    fn local_link(project: &Path) -> std::path::PathBuf {
+        project.join(".sdkman/candidates/java/current")
+    }
+
+    #[test]
+    #[serial]
+    fn test_refuses_to_remove_current_without_flags() {
+        let (_temp, _project, pwd) = setup();
+        let mut messages = Vec::new();
+
+        let err = release_current("java", &removing("17.0.9"), None, false, &mut messages).unwrap_err();
+        assert!(err.msg.contains("--switch-to"), "{}", err.msg);
+        assert_eq!(env::get_current_version("java"), Some("17.0.9".to_string()));
+
+        // Versions that aren't current need no flags
+        release_current("java", &removing("21.0.1"), None, false, &mut messages).unwrap();
+        assert!(messages.is_empty());
+
+        teardown(pwd);
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_to_rejects_removed_or_missing_versions() {
+        let (_temp, _project, pwd) = setup();
+        let mut messages = Vec::new();
+        let both = vec!["17.0.9".to_string(), "21.0.1".to_string()];
+
+        let err = release_current("java", &both, Some("21.0.1"), false, &mut messages).unwrap_err();
+        assert!(err.msg.contains("being uninstalled"), "{}", err.msg);
+        let err = release_current("java", &removing("17.0.9"), Some("11.0.1"), false, &mut messages).unwrap_err();
+        assert!(err.msg.contains("not installed"), "{}", err.msg);
+        assert_eq!(env::get_current_version("java"), Some("17.0.9".to_string()));
+
+        release_current("java", &removing("17.0.9"), Some("21.0.1"), false, &mut messages).unwrap();
+        assert_eq!(env::get_current_version("java"), Some("21.0.1".to_string()));
+        assert_eq!(messages, vec!["Switched java to 21.0.1"]);
+
+        teardown(pwd);
+    }
+
+    #[test]
+    #[serial]
+    fn test_force_unlinks_current() {
+        let (_temp, _project, pwd) = setup();
+        let mut messages = Vec::new();
+
+        release_current("java", &removing("17.0.9"), None, true, &mut messages).unwrap();
+        assert_eq!(env::get_current_version("java"), None);
+        assert!(env::is_installed("java", "17.0.9"));
+
+        teardown(pwd);
+    }
+
+    #[test]
+    #[serial]
+    fn test_local_link_is_switched_or_unlinked() {
+        let (_temp, project, pwd) = setup();
+        fs::create_dir_all(project.path().join(".sdkman")).unwrap();
+        env::set_current_version("java", "21.0.1").unwrap();
+        env::set_local_current_version("java", "17.0.9").unwrap();
+        let mut messages = Vec::new();
+
+        // Only the local environment links 17
+        let err = release_current("java", &removing("17.0.9"), None, false, &mut messages).unwrap_err();
+        assert!(err.msg.contains("local .sdkman"), "{}", err.msg);
+
+        release_current("java", &removing("17.0.9"), Some("21.0.1"), false, &mut messages).unwrap();
+        assert_eq!(env::get_local_current_version("java"), Some("21.0.1".to_string()));
+        assert_eq!(messages, vec!["Switched local java to 21.0.1"]);
+
+        release_current("java", &removing("21.0.1"), None, true, &mut messages).unwrap();
+        assert_eq!(env::get_local_current_version("java"), None);
+        assert_eq!(env::get_current_version("java"), None);
+        assert!(!local_link(project.path()).exists());
+
+        teardown(pwd);
+    }
+}
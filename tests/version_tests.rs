@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use nu_plugin_sdkman::core::api;
+    use nu_plugin_sdkman::core::version::{self, UpgradePolicy, VersionId, Within};
+
+    const JAVA_VERSIONS: &str = "25.0.1-tem,25.0.1-zulu,21.0.9-tem,21.0.9-oracle,17.0.17-tem,17.0.12-tem,17.0.17-zulu,17.0.9-tem,11.0.29-tem,26.ea.13-open";
+
+    #[test]
+    fn test_parse_version_id() {
+        let v = VersionId::parse("17.0.9-tem");
+        assert_eq!(v.numbers, vec![17, 0, 9]);
+        assert_eq!(v.qualifier, "");
+        assert_eq!(v.vendor, "tem");
+
+        let v = VersionId::parse("23.1.9.r21-mandrel");
+        assert_eq!(v.numbers, vec![23, 1, 9]);
+        assert_eq!(v.qualifier, "r21");
+        assert_eq!(v.vendor, "mandrel");
+
+        let v = VersionId::parse("8.5");
+        assert_eq!(v.numbers, vec![8, 5]);
+        assert_eq!(v.vendor, "");
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let a = VersionId::parse("17.0.10-tem");
+        let b = VersionId::parse("17.0.9-tem");
+        assert_eq!(a.cmp_version(&b), Ordering::Greater);
+
+        let release = VersionId::parse("26-open");
+        let ea = VersionId::parse("26.ea.13-open");
+        assert!(ea.is_prerelease());
+        assert_eq!(release.cmp_version(&ea), Ordering::Greater);
+
+        assert_eq!(VersionId::parse("8.5").cmp_version(&VersionId::parse("8.5.0")), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_java_lts() {
+        for lts in ["8.0.392-tem", "11.0.29-tem", "17.0.9-tem", "21.0.9-tem", "25.0.1-tem"] {
+            assert!(version::is_lts("java", &VersionId::parse(lts)), "{} should be LTS", lts);
+        }
+        for non_lts in ["22.0.2-tem", "23.0.1-tem", "24.0.2-tem"] {
+            assert!(!version::is_lts("java", &VersionId::parse(non_lts)), "{} should not be LTS", non_lts);
+        }
+        assert!(version::is_lts("gradle", &VersionId::parse("8.5")));
+    }
+
+    #[test]
+    fn test_upgrade_stays_within_major_and_vendor() {
+        let available = api::parse_versions_text(JAVA_VERSIONS);
+        let target = version::select_upgrade("java", "17.0.9-tem", &available, &UpgradePolicy::default());
+        assert_eq!(target, Some("17.0.17-tem".to_string()));
+    }
+
+    #[test]
+    fn test_default_scope_only_pins_java_major() {
+        let available = api::parse_versions_text("9.2.0,8.14.3,8.5");
+        let target = version::select_upgrade("gradle", "8.5", &available, &UpgradePolicy::default());
+        assert_eq!(target, Some("9.2.0".to_string()));
+
+        let policy = UpgradePolicy { within: Some(Within::Major), ..Default::default() };
+        let target = version::select_upgrade("gradle", "8.5", &available, &policy);
+        assert_eq!(target, Some("8.14.3".to_string()));
+    }
+
+    #[test]
+    fn test_upgrade_any_scope_keeps_vendor() {
+        let available = api::parse_versions_text(JAVA_VERSIONS);
+        let policy = UpgradePolicy { within: Some(Within::Any), ..Default::default() };
+        let target = version::select_upgrade("java", "17.0.9-zulu", &available, &policy);
+        assert_eq!(target, Some("25.0.1-zulu".to_string()));
+    }
+
+    #[test]
+    fn test_upgrade_lts_only_skips_prereleases() {
+        let available = api::parse_versions_text("26.ea.13-tem,24.0.2-tem,21.0.9-tem,17.0.9-tem");
+        let policy = UpgradePolicy { within: Some(Within::Any), same_vendor: false, lts_only: true };
+        let target = version::select_upgrade("java", "17.0.9-tem", &available, &policy);
+        assert_eq!(target, Some("21.0.9-tem".to_string()));
+    }
+
+    #[test]
+    fn test_upgrade_already_latest() {
+        let available = api::parse_versions_text(JAVA_VERSIONS);
+        let target = version::select_upgrade("java", "17.0.17-tem", &available, &UpgradePolicy::default());
+        assert_eq!(target, None);
+    }
+
+    #[test]
+    fn test_match_version_spec() {
+        let installed: Vec<String> = JAVA_VERSIONS.split(',').map(String::from).collect();
+        assert_eq!(version::match_version("17.0.9-tem", &installed), Some("17.0.9-tem".to_string()));
+        assert_eq!(version::match_version("17", &installed), Some("17.0.17-zulu".to_string()));
+        assert_eq!(version::match_version("17-tem", &installed), Some("17.0.17-tem".to_string()));
+        assert_eq!(version::match_version("21.0-oracle", &installed), Some("21.0.9-oracle".to_string()));
+        assert_eq!(version::match_version("1", &installed), None);
+        assert_eq!(version::match_version("tem", &installed), None);
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+    use serial_test::serial;
+    use nu_plugin_sdkman::core::env;
+    use nu_plugin_sdkman::core::which::{self, BinarySource};
+
+    #[cfg(unix)]
+    fn fake_binary(dir: &Path, name: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(name), "").unwrap();
+        fs::set_permissions(dir.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_which_reports_local_global_and_system() {
+        let temp = tempdir().unwrap();
+        let global = temp.path().join("global");
+        std::env::set_var("SDKMAN_DIR", &global);
+
+        fake_binary(&global.join("candidates/java/17.0.9/bin"), "java");
+        std::os::unix::fs::symlink(global.join("candidates/java/17.0.9"), global.join("candidates/java/current")).unwrap();
+
+        let project = temp.path().join("project");
+        let local = project.join(".sdkman/candidates/java");
+        fake_binary(&local.join("21.0.1/bin"), "java");
+        std::os::unix::fs::symlink(local.join("21.0.1"), local.join("current")).unwrap();
+
+        let system = temp.path().join("usr/bin");
+        fake_binary(&system, "java");
+
+        let path = std::env::join_paths([
+            local.join("current/bin"),
+            global.join("candidates/java/current/bin"),
+            system.clone(),
+        ])
+        .unwrap();
+        let found = which::which("java", path.to_str(), &project);
+
+        let summary: Vec<(BinarySource, Option<&str>)> = found.iter().map(|l| (l.source, l.version.as_deref())).collect();
+        assert_eq!(summary, vec![
+            (BinarySource::Local, Some("21.0.1")),
+            (BinarySource::Global, Some("17.0.9")),
+            (BinarySource::System, None),
+        ]);
+        assert_eq!(found[0].candidate.as_deref(), Some("java"));
+        assert_eq!(found[2].path, system.join("java"));
+
+        // Shadowed SDKMAN binaries are found even when not on PATH
+        let found = which::which("java", system.to_str(), &project);
+        let sources: Vec<BinarySource> = found.iter().map(|l| l.source).collect();
+        assert_eq!(sources, vec![BinarySource::System, BinarySource::Local, BinarySource::Global]);
+
+        assert!(which::which("missing", path.to_str(), &project).is_empty());
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_which_skips_non_executable_files() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path().join("global"));
+        let docs = temp.path().join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("java"), "not a program").unwrap();
+        let system = temp.path().join("usr/bin");
+        fake_binary(&system, "java");
+
+        let path = std::env::join_paths([&docs, &system]).unwrap();
+        let found = which::which("java", path.to_str(), temp.path());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, system.join("java"));
+        assert_eq!(which::executable_in(&docs, "java"), None);
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_which_from_project_subdirectory() {
+        let temp = tempdir().unwrap();
+        std::env::set_var("SDKMAN_DIR", temp.path().join("global"));
+
+        let project = temp.path().join("project");
+        let local = project.join(".sdkman/candidates/java");
+        fake_binary(&local.join("21.0.1/bin"), "java");
+        std::os::unix::fs::symlink(local.join("21.0.1"), local.join("current")).unwrap();
+        fs::write(project.join(".sdkmanrc"), "java=21.0.1\n").unwrap();
+        let subdir = project.join("module/src");
+        fs::create_dir_all(&subdir).unwrap();
+
+        let root = env::project_root(&subdir);
+        let found = which::which("java", local.join("current/bin").to_str(), &root);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].source, BinarySource::Local);
+        assert_eq!(found[0].version.as_deref(), Some("21.0.1"));
+
+        // Local current/bin directories are found without being on PATH
+        assert_eq!(which::which("java", None, &root).len(), 1);
+
+        std::env::remove_var("SDKMAN_DIR");
+    }
+}